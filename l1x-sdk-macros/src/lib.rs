@@ -1,16 +1,170 @@
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
+use quote::{format_ident, quote};
+use syn::AttributeArgs;
 use syn::ItemImpl;
 use syn::Signature;
 use syn::Visibility;
 
-fn input_struct_deser(sig: &Signature) -> TokenStream2 {
+/// The format the generated wrappers use to deserialize method arguments.
+enum InputFormat {
+    Json,
+    Borsh,
+}
+
+impl InputFormat {
+    fn name(&self) -> &'static str {
+        match self {
+            InputFormat::Json => "json",
+            InputFormat::Borsh => "borsh",
+        }
+    }
+}
+
+/// Arguments of the `#[contract]` attribute.
+struct ContractArgs {
+    /// `input = "json" | "borsh"`. Defaults to JSON
+    input_format: InputFormat,
+    /// `abi`. Generates `__contract_abi()` if set
+    abi: bool,
+    /// Cleared by `no_reentrancy_guard`
+    reentrancy_guard: bool,
+    /// `default_state`. Falls back to `Default` if the state has never been written
+    default_state: bool,
+}
+
+impl ContractArgs {
+    fn from_attr_args(args: AttributeArgs) -> syn::Result<Self> {
+        let mut contract_args = ContractArgs {
+            input_format: InputFormat::Json,
+            abi: false,
+            reentrancy_guard: true,
+            default_state: false,
+        };
+        for arg in args {
+            match arg {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(value),
+                    ..
+                })) if path.is_ident("input") => {
+                    contract_args.input_format = match value.value().as_str() {
+                        "json" => InputFormat::Json,
+                        "borsh" => InputFormat::Borsh,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                value,
+                                "Expected \"json\" or \"borsh\".",
+                            ))
+                        }
+                    }
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("abi") => {
+                    contract_args.abi = true
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path))
+                    if path.is_ident("no_reentrancy_guard") =>
+                {
+                    contract_args.reentrancy_guard = false
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("default_state") => {
+                    contract_args.default_state = true
+                }
+                arg => {
+                    return Err(syn::Error::new_spanned(
+                        arg,
+                        "Unsupported #[contract] argument. Expected `input = \"json\"`, `input = \"borsh\"`, `abi`, `no_reentrancy_guard` or `default_state`.",
+                    ))
+                }
+            }
+        }
+        Ok(contract_args)
+    }
+}
+
+/// Rejects impls with generic parameters: the exported `extern "C"` wrappers can't be generic, so
+/// they need a concrete type to load the state into and to call the methods on.
+fn check_impl_generics(input: &ItemImpl) -> syn::Result<()> {
+    if input.generics.params.is_empty() {
+        return Ok(());
+    }
+    Err(syn::Error::new_spanned(
+        &input.generics,
+        "#[contract] can't be used on generic impls because the exported functions can't be generic. Implement the contract for a concrete type instead, e.g. `impl Contract<u64>`.",
+    ))
+}
+
+/// Escapes `s` as a JSON string literal.
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Renders a type the way it is usually written, e.g. `Vec<u8>` rather than `Vec < u8 >`.
+fn type_name(ty: &syn::Type) -> String {
+    quote!(#ty)
+        .to_string()
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace(" :: ", "::")
+        .replace("& ", "&")
+}
+
+/// Describes the method as a JSON object of the contract ABI.
+fn method_abi(sig: &Signature, name: &syn::Ident, is_view: bool) -> String {
+    let mut receiver = "null".to_string();
+    let mut args = Vec::new();
+    for arg in &sig.inputs {
+        match arg {
+            syn::FnArg::Receiver(r) => {
+                let r = if r.mutability.is_some() {
+                    "&mut self"
+                } else {
+                    "&self"
+                };
+                receiver = json_string(r);
+            }
+            syn::FnArg::Typed(typed) => {
+                let pat = &typed.pat;
+                args.push(format!(
+                    "{{\"name\":{},\"type\":{}}}",
+                    json_string(&quote!(#pat).to_string()),
+                    json_string(&type_name(&typed.ty))
+                ));
+            }
+        }
+    }
+    let returns = match &sig.output {
+        syn::ReturnType::Default => "null".to_string(),
+        syn::ReturnType::Type(_, ty) => json_string(&type_name(ty)),
+    };
+    format!(
+        "{{\"name\":{},\"view\":{},\"receiver\":{},\"args\":[{}],\"returns\":{}}}",
+        json_string(&name.to_string()),
+        is_view,
+        receiver,
+        args.join(","),
+        returns
+    )
+}
+
+fn input_struct_deser(sig: &Signature, format: &InputFormat) -> TokenStream2 {
     let mut fields = TokenStream2::new();
     for arg in &sig.inputs {
         match arg {
-            syn::FnArg::Receiver(_) => todo!(),
+            syn::FnArg::Receiver(_) => continue,
             syn::FnArg::Typed(typed) => {
                 let ident = &typed.pat;
                 let ty = &typed.ty;
@@ -20,19 +174,115 @@ fn input_struct_deser(sig: &Signature) -> TokenStream2 {
             }
         }
     }
+    let derive = match format {
+        InputFormat::Json => quote! { serde::Deserialize },
+        InputFormat::Borsh => quote! { borsh::BorshDeserialize },
+    };
     quote! {
-        #[derive(serde::Deserialize)]
+        #[derive(#derive)]
         struct Input {
             #fields
         }
     }
 }
 
+/// Returns `true` if `ty` is spelled as `Result<..>`, e.g. `Result<u64, String>` or `std::result::Result<u64, String>`.
+fn is_result_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Result")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Removes the `#[name]` marker, e.g. `#[view]`, from the method attributes, returning `true` if it was present.
+fn take_marker_attr(method: &mut syn::ImplItemMethod, name: &str) -> bool {
+    let attrs_len = method.attrs.len();
+    method.attrs.retain(|attr| !attr.path.is_ident(name));
+    method.attrs.len() != attrs_len
+}
+
+/// Removes the `#[method_name = "..."]` attribute from the method attributes, returning the name
+/// it sets.
+fn take_method_name_attr(method: &mut syn::ImplItemMethod) -> syn::Result<Option<syn::Ident>> {
+    let Some(position) = method
+        .attrs
+        .iter()
+        .position(|attr| attr.path.is_ident("method_name"))
+    else {
+        return Ok(None);
+    };
+    let attr = method.attrs.remove(position);
+    match attr.parse_meta()? {
+        syn::Meta::NameValue(syn::MetaNameValue {
+            lit: syn::Lit::Str(value),
+            ..
+        }) => value
+            .parse()
+            .map(Some)
+            .map_err(|_| syn::Error::new_spanned(value, "Expected a valid function name.")),
+        meta => Err(syn::Error::new_spanned(
+            meta,
+            "Expected #[method_name = \"...\"].",
+        )),
+    }
+}
+
 /// Walks over public methods and generates wrappers for each method it finds.
 ///
 /// The generated wrapper reads method arguments [`l1x_sdk::input`], deserializes them, and calls the original method.
 /// When the original method returns, the wrapper serializes the returned value and writes the serialized value with `l1x_sdk::output`
 ///
+/// Methods may take `&self` or `&mut self`. For such methods the wrapper loads the contract state with
+/// `l1x_sdk::state_read` and calls the method on it. The call panics if the state has never been written, so the
+/// contract has to write its initial state with `l1x_sdk::state_write` first, e.g. from an exported constructor. Use
+/// `#[contract(default_state)]` to fall back to [`Default`] instead, the contract type must then implement it.
+/// For `&mut self` methods the state is written back with `l1x_sdk::state_write` before the wrapper returns.
+///
+/// If a method returns `Result<T, E>`, only `T` is serialized on `Ok`. On `Err(e)` the wrapper calls
+/// `l1x_sdk::panic` with `e.to_string()`, so the call fails and the transaction is reverted. `E` must therefore
+/// implement [`ToString`], for example through [`std::fmt::Display`]. For `&mut self` methods the state is not written
+/// back on `Err`.
+///
+/// Unless `#[contract(no_reentrancy_guard)]` is set, each wrapper holds the reentrancy guard of the contract instance
+/// while the method runs, so any call back into the same contract instance fails. See
+/// `l1x_sdk::reentrancy_guard_enter` for the exact policy.
+///
+/// Read-only methods can be marked with `#[view]`. The wrapper of a view method never writes to the storage: it
+/// doesn't set the reentrancy guard (it only checks that no guard is set). This makes view methods callable in a
+/// read-only context. View methods must take `&self`, marking a `&mut self` method with `#[view]` is an error.
+///
+/// Only `pub` methods are exported. Mark a `pub` method with `#[private]` to keep it callable from Rust without
+/// exporting it, or mark a non-`pub` method with `#[export]` to export it anyway.
+///
+/// The wrapper is a `pub extern "C" fn __l1x_export_<name>()` next to the impl, so it can't clash with other items of
+/// the module, and is exported from the wasm module under the method name. Use `#[method_name = "transfer_v2"]` to export it under another
+/// name while keeping the Rust name of the method, e.g. to keep an old entry point after an upgrade or to export
+/// methods with the same name from different impls.
+///
+/// Arguments are deserialized from JSON by default. Use `#[contract(input = "borsh")]` to deserialize them with Borsh
+/// instead, so argument types only need to implement `BorshDeserialize`. The arguments are then expected as the Borsh
+/// encoding of a struct with one field per argument, in declaration order.
+///
+/// `#[contract(abi)]` additionally generates `pub fn __contract_abi() -> String` that returns a JSON description of the
+/// public methods, for client generators and other tooling:
+///
+/// ```json
+/// {
+///   "input": "json",
+///   "output": "json",
+///   "methods": [
+///     {"name": "add", "view": false, "receiver": "&mut self", "args": [{"name": "amount", "type": "u64"}], "returns": null}
+///   ]
+/// }
+/// ```
+///
+/// Types are recorded as written in the source. `receiver` is `null` for associated functions.
+///
 /// # Example
 /// ```
 /// use l1x_sdk_macros::contract;
@@ -46,23 +296,180 @@ fn input_struct_deser(sig: &Signature) -> TokenStream2 {
 ///     }
 /// }
 /// ```
+///
+/// Methods with a receiver:
+/// ```
+/// use l1x_sdk_macros::contract;
+/// use borsh::{BorshDeserialize, BorshSerialize};
+///
+/// #[derive(BorshSerialize, BorshDeserialize)]
+/// struct Counter {
+///     value: u64,
+/// }
+///
+/// #[contract]
+/// impl Counter {
+///     pub fn new() {
+///         l1x_sdk::state_write(&Counter { value: 0 });
+///     }
+///
+///     pub fn increment(&mut self) {
+///         self.value += 1;
+///     }
+///
+///     #[view]
+///     pub fn get(&self) -> u64 {
+///         self.value
+///     }
+/// }
+/// ```
+///
+/// Falling back to the default state, so no constructor has to be called first:
+/// ```
+/// use l1x_sdk_macros::contract;
+/// use borsh::{BorshDeserialize, BorshSerialize};
+///
+/// #[derive(BorshSerialize, BorshDeserialize, Default)]
+/// struct Counter {
+///     value: u64,
+/// }
+///
+/// #[contract(default_state)]
+/// impl Counter {
+///     pub fn increment(&mut self) {
+///         self.value += 1;
+///     }
+/// }
+/// ```
+///
+/// Borsh encoded arguments:
+/// ```
+/// use l1x_sdk_macros::contract;
+/// use borsh::BorshDeserialize;
+///
+/// #[derive(BorshDeserialize)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// struct Contract {};
+///
+/// #[contract(input = "borsh")]
+/// impl Contract {
+///     pub fn sum(point: Point) -> u32 {
+///         point.x + point.y
+///     }
+/// }
+/// ```
+///
+/// The contract type may be a generic type with concrete parameters, but the impl itself can't
+/// have generic parameters:
+/// ```
+/// use l1x_sdk_macros::contract;
+/// use borsh::{BorshDeserialize, BorshSerialize};
+///
+/// #[derive(BorshSerialize, BorshDeserialize)]
+/// struct Store<T> {
+///     value: T,
+/// }
+///
+/// #[contract]
+/// impl Store<u64> {
+///     pub fn new_value() -> u64 {
+///         0
+///     }
+///
+///     #[view]
+///     pub fn get(&self) -> u64 {
+///         self.value
+///     }
+/// }
+/// ```
+///
+/// `#[view]` methods can't modify the state:
+/// ```compile_fail
+/// use l1x_sdk_macros::contract;
+/// use borsh::{BorshDeserialize, BorshSerialize};
+///
+/// #[derive(BorshSerialize, BorshDeserialize, Default)]
+/// struct Counter {
+///     value: u64,
+/// }
+///
+/// #[contract]
+/// impl Counter {
+///     #[view]
+///     pub fn increment(&mut self) -> u64 {
+///         self.value += 1;
+///         self.value
+///     }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use l1x_sdk_macros::contract;
+///
+/// #[derive(Default)]
+/// struct Store<T> {
+///     value: T,
+/// }
+///
+/// #[contract]
+/// impl<T> Store<T> {
+///     pub fn get(&self) -> u64 {
+///         0
+///     }
+/// }
+/// ```
 #[proc_macro_attribute]
-pub fn contract(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    if let Ok(input) = syn::parse::<ItemImpl>(item) {
+pub fn contract(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let contract_args =
+        match ContractArgs::from_attr_args(syn::parse_macro_input!(attr as AttributeArgs)) {
+            Ok(args) => args,
+            Err(err) => return TokenStream::from(err.to_compile_error()),
+        };
+    let input_format = &contract_args.input_format;
+    if let Ok(mut input) = syn::parse::<ItemImpl>(item) {
+        if let Err(err) = check_impl_generics(&input) {
+            return TokenStream::from(err.to_compile_error());
+        }
         let struct_type = &input.self_ty;
         let mut generated_code = TokenStream2::new();
-        for item in &input.items {
+        let mut methods_abi = Vec::new();
+        for item in &mut input.items {
             match item {
                 syn::ImplItem::Method(method) => {
-                    if !matches!(method.vis, Visibility::Public(_)) {
+                    let is_view = take_marker_attr(method, "view");
+                    let is_private = take_marker_attr(method, "private");
+                    let is_export = take_marker_attr(method, "export");
+                    let method_name = match take_method_name_attr(method) {
+                        Ok(method_name) => method_name,
+                        Err(err) => return TokenStream::from(err.to_compile_error()),
+                    };
+                    if is_private && is_export {
+                        return TokenStream::from(
+                            syn::Error::new_spanned(
+                                &method.sig.ident,
+                                "A method can't be both #[private] and #[export].",
+                            )
+                            .to_compile_error(),
+                        );
+                    }
+                    let exported =
+                        is_export || (!is_private && matches!(method.vis, Visibility::Public(_)));
+                    if !exported {
                         continue;
                     }
                     let ident = &method.sig.ident;
-                    let arg_struct = input_struct_deser(&method.sig);
+                    let export_ident = method_name.unwrap_or_else(|| ident.clone());
+                    methods_abi.push(method_abi(&method.sig, &export_ident, is_view));
+                    let arg_struct = input_struct_deser(&method.sig, input_format);
                     let mut arg_list = TokenStream2::new();
+                    let mut receiver = None;
                     for arg in &method.sig.inputs {
                         match arg {
-                            syn::FnArg::Receiver(_) => todo!(),
+                            syn::FnArg::Receiver(r) => receiver = Some(r),
                             syn::FnArg::Typed(typed) => {
                                 let ident = &typed.pat;
                                 arg_list.extend(quote! {
@@ -71,41 +478,115 @@ pub fn contract(_attr: TokenStream, item: TokenStream) -> TokenStream {
                             }
                         }
                     }
+                    let unwrap_result = match &method.sig.output {
+                        syn::ReturnType::Type(_, ty) if is_result_type(ty) => quote! {
+                            let result = match result {
+                                Ok(value) => value,
+                                Err(err) => l1x_sdk::panic(&err.to_string()),
+                            };
+                        },
+                        _ => quote! {},
+                    };
+                    let export_name = export_ident.to_string();
+                    let state_read = if contract_args.default_state {
+                        quote! { l1x_sdk::state_read().unwrap_or_default() }
+                    } else {
+                        quote! { l1x_sdk::__contract_state_read(#export_name) }
+                    };
+                    let method_call = match receiver {
+                        None => quote! {
+                            let result = <#struct_type>::#ident(#arg_list);
+                            #unwrap_result
+                        },
+                        Some(syn::Receiver {
+                            reference: Some(_),
+                            mutability: None,
+                            ..
+                        }) => quote! {
+                            let state: #struct_type = #state_read;
+                            let result = state.#ident(#arg_list);
+                            #unwrap_result
+                        },
+                        Some(
+                            receiver @ syn::Receiver {
+                                reference: Some(_),
+                                mutability: Some(_),
+                                ..
+                            },
+                        ) if is_view => {
+                            return TokenStream::from(
+                                syn::Error::new_spanned(
+                                    receiver,
+                                    "#[view] methods must take &self",
+                                )
+                                .to_compile_error(),
+                            )
+                        }
+                        Some(syn::Receiver {
+                            reference: Some(_),
+                            mutability: Some(_),
+                            ..
+                        }) => quote! {
+                            let mut state: #struct_type = #state_read;
+                            let result = state.#ident(#arg_list);
+                            #unwrap_result
+                            l1x_sdk::state_write(&state);
+                        },
+                        Some(receiver) => {
+                            return TokenStream::from(
+                                syn::Error::new_spanned(
+                                    receiver,
+                                    "#[contract] methods can only take `&self` or `&mut self`.",
+                                )
+                                .to_compile_error(),
+                            )
+                        }
+                    };
+                    let wrapper_ident = format_ident!("__l1x_export_{}", export_ident);
+                    let input_deserialization = match input_format {
+                        InputFormat::Json => quote! {
+                            l1x_sdk::__method_input_json(#export_name)
+                        },
+                        InputFormat::Borsh => quote! {
+                            l1x_sdk::__method_input_borsh(#export_name)
+                        },
+                    };
                     let ouput_serialization = match method.sig.output {
                         syn::ReturnType::Default => quote! {},
                         syn::ReturnType::Type(_, _) => quote! {
-                            let result = serde_json::to_vec(&result).expect("Failed to serialize the return value using JSON.");
-                            l1x_sdk::output(&result);
+                            l1x_sdk::__method_output_json(#export_name, &result);
                         },
                     };
+                    let write_perm = if is_view {
+                        quote! { false }
+                    } else {
+                        quote! { l1x_sdk::storage_write_perm() }
+                    };
+                    let (guard_enter, guard_exit) = if contract_args.reentrancy_guard {
+                        (
+                            quote! {
+                                let write_perm = #write_perm;
+                                l1x_sdk::reentrancy_guard_enter(write_perm);
+                            },
+                            quote! {
+                                l1x_sdk::reentrancy_guard_exit(write_perm);
+                            },
+                        )
+                    } else {
+                        (quote! {}, quote! {})
+                    };
                     generated_code.extend(quote! {
-                        #[cfg(target_arch = "wasm32")]
-                        #[no_mangle]
-                        pub extern "C" fn #ident() {
-                            let REENTRANCY_GUARD_KEY: &[u8] = b"__REENTRANCY_GUARD__";
-                            let REENTRANCY_GUARD: &[u8] = b"";
+                        #[cfg_attr(target_arch = "wasm32", export_name = #export_name)]
+                        pub extern "C" fn #wrapper_ident() {
                             l1x_sdk::setup_panic_hook();
-                            let write_perm = l1x_sdk::storage_write_perm();
-                            if write_perm {
-                                if l1x_sdk::storage_write(&REENTRANCY_GUARD_KEY, REENTRANCY_GUARD) {
-                                    panic!("Found a cross-contract call loop");
-                                }
-                            } else {
-                                if l1x_sdk::storage_read(&REENTRANCY_GUARD_KEY).is_some() {
-                                    panic!("Found a cross-contract call loop");
-                                }
-                            }
+                            #guard_enter
                             #arg_struct
                             let Input {
                                 #arg_list
-                            } = serde_json::from_slice(
-                                &l1x_sdk::input().expect("Expected input since method has arguments.")
-                            ).expect("Failed to deserialize input from JSON.");
-                            let result = #struct_type::#ident(#arg_list);
+                            } = #input_deserialization;
+                            #method_call
                             #ouput_serialization
-                            if write_perm {
-                                l1x_sdk::storage_remove(&REENTRANCY_GUARD_KEY);
-                            }
+                            #guard_exit
                         }
                     })
                 }
@@ -121,6 +602,20 @@ pub fn contract(_attr: TokenStream, item: TokenStream) -> TokenStream {
             }
         }
 
+        if contract_args.abi {
+            let abi = format!(
+                "{{\"input\":{},\"output\":\"json\",\"methods\":[{}]}}",
+                json_string(input_format.name()),
+                methods_abi.join(",")
+            );
+            generated_code.extend(quote! {
+                /// Returns the JSON description of the contract methods generated by `#[contract(abi)]`.
+                pub fn __contract_abi() -> String {
+                    #abi.to_string()
+                }
+            });
+        }
+
         TokenStream::from(quote! {
             #input
             #generated_code
@@ -135,3 +630,213 @@ pub fn contract(_attr: TokenStream, item: TokenStream) -> TokenStream {
         )
     }
 }
+
+/// Returns the version set with `#[event(version = N)]`, `1` if the attribute is absent.
+fn event_version(attrs: &[syn::Attribute]) -> syn::Result<u8> {
+    let mut version = 1;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("event")) {
+        match attr.parse_meta()? {
+            syn::Meta::List(list) => {
+                for arg in list.nested {
+                    match arg {
+                        syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                            path,
+                            lit: syn::Lit::Int(value),
+                            ..
+                        })) if path.is_ident("version") => version = value.base10_parse()?,
+                        arg => {
+                            return Err(syn::Error::new_spanned(
+                                arg,
+                                "Unsupported #[event] argument. Expected `version = N`.",
+                            ))
+                        }
+                    }
+                }
+            }
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "Expected #[event(version = N)].",
+                ))
+            }
+        }
+    }
+    Ok(version)
+}
+
+/// Implements `l1x_sdk::Event` for the type.
+///
+/// `NAME` is the name of the type. `VERSION` is `1` unless it's set with `#[event(version = N)]`.
+///
+/// # Example
+/// ```
+/// use borsh::BorshSerialize;
+/// use l1x_sdk::Event;
+///
+/// #[derive(BorshSerialize, Event)]
+/// #[event(version = 2)]
+/// struct Transfer {
+///     amount: u64,
+/// }
+///
+/// assert_eq!(Transfer::NAME, "Transfer");
+/// assert_eq!(Transfer::VERSION, 2);
+/// ```
+#[proc_macro_derive(Event, attributes(event))]
+pub fn derive_event(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+    let version = match event_version(&input.attrs) {
+        Ok(version) => version,
+        Err(err) => return TokenStream::from(err.to_compile_error()),
+    };
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    TokenStream::from(quote! {
+        impl #impl_generics l1x_sdk::Event for #ident #ty_generics #where_clause {
+            const NAME: &'static str = #name;
+            const VERSION: u8 = #version;
+        }
+    })
+}
+
+fn storage_key_impl(input: &syn::DeriveInput) -> syn::Result<TokenStream2> {
+    let syn::Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "#[derive(StorageKey)] can only be used on enums.",
+        ));
+    };
+    if data.variants.len() > 256 {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "#[derive(StorageKey)] supports at most 256 variants.",
+        ));
+    }
+
+    let mut arms = Vec::new();
+    for (i, variant) in data.variants.iter().enumerate() {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "#[derive(StorageKey)] only supports unit variants. Use `l1x_sdk::store::nested_prefix` for nested collections.",
+            ));
+        }
+        let ident = &variant.ident;
+        let byte = i as u8;
+        arms.push(quote! { Self::#ident => #byte });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics l1x_sdk::store::StorageKey for #ident #ty_generics #where_clause {
+            fn prefix_byte(&self) -> u8 {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+    })
+}
+
+/// Implements `l1x_sdk::store::StorageKey` for an enum of unit variants.
+///
+/// The variants get the prefixes `0`, `1`, `2`, ... in the declaration order. Only append new
+/// variants to the end, otherwise the existing collections of a deployed contract change prefixes.
+///
+/// # Example
+/// ```
+/// use l1x_sdk::store::{prefix_for, StorageKey};
+///
+/// #[derive(StorageKey)]
+/// enum Keys {
+///     Balances,
+///     Holders,
+/// }
+///
+/// assert_eq!(prefix_for(Keys::Holders), vec![1]);
+/// ```
+///
+/// Variants with fields are rejected:
+/// ```compile_fail
+/// use l1x_sdk::store::StorageKey;
+///
+/// #[derive(StorageKey)]
+/// enum Keys {
+///     Accounts { id: u64 },
+/// }
+/// ```
+#[proc_macro_derive(StorageKey)]
+pub fn derive_storage_key(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::DeriveInput);
+    match storage_key_impl(&input) {
+        Ok(tokens) => TokenStream::from(tokens),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_impl_generics() {
+        let concrete: ItemImpl = syn::parse_quote! {
+            impl Store<u64> where u64: Copy {
+                pub fn get(&self) -> u64 { self.value }
+            }
+        };
+        assert!(check_impl_generics(&concrete).is_ok());
+
+        let generic: ItemImpl = syn::parse_quote! {
+            impl<T: Default> Store<T> {
+                pub fn get(&self) -> u64 { 0 }
+            }
+        };
+        let err = check_impl_generics(&generic).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("#[contract] can't be used on generic impls"));
+    }
+
+    #[test]
+    fn test_take_method_name_attr() {
+        let mut method: syn::ImplItemMethod = syn::parse_quote! {
+            #[method_name = "transfer_v2"]
+            pub fn transfer(&mut self) {}
+        };
+        let name = take_method_name_attr(&mut method).unwrap();
+        assert_eq!(name.unwrap().to_string(), "transfer_v2");
+        assert!(method.attrs.is_empty());
+
+        let mut method: syn::ImplItemMethod = syn::parse_quote! {
+            #[method_name = "not a name"]
+            pub fn transfer(&mut self) {}
+        };
+        assert!(take_method_name_attr(&mut method).is_err());
+    }
+
+    #[test]
+    fn test_storage_key_impl() {
+        let unit: syn::DeriveInput = syn::parse_quote! {
+            enum Keys { Balances, Holders }
+        };
+        let tokens = storage_key_impl(&unit).unwrap().to_string();
+        assert!(tokens.contains("Self :: Balances => 0u8"));
+        assert!(tokens.contains("Self :: Holders => 1u8"));
+
+        let with_fields: syn::DeriveInput = syn::parse_quote! {
+            enum Keys { Balances, Accounts(u64) }
+        };
+        assert!(storage_key_impl(&with_fields).is_err());
+
+        let not_enum: syn::DeriveInput = syn::parse_quote! {
+            struct Keys;
+        };
+        assert!(storage_key_impl(&not_enum).is_err());
+    }
+}
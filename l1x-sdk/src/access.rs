@@ -0,0 +1,147 @@
+//! Role-based access control.
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::store::LookupMap;
+use crate::types::Address;
+
+const ERR_MISSING_ROLE: &str = "The caller doesn't have the required role";
+
+/// Assigns roles to addresses.
+///
+/// `R` identifies a role, usually a small enum or a `String`:
+///
+/// ```ignore
+/// #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+/// enum Role {
+///     Admin,
+///     Minter,
+/// }
+///
+/// impl Token {
+///     pub fn add_minter(&mut self, minter: Address) {
+///         self.roles.assert_role(&Role::Admin);
+///         self.roles.grant(Role::Minter, minter);
+///     }
+///
+///     pub fn mint(&mut self, amount: u128) {
+///         self.roles.assert_role(&Role::Minter);
+///         // ...
+///     }
+/// }
+/// ```
+///
+/// Granting and revoking roles isn't restricted by itself, guard these methods of the contract with
+/// [`Self::assert_role`] or [`crate::assert_owner`].
+///
+/// The roles are stored in a [`LookupMap`] keyed by `(role, address)`, so the members of a role can't
+/// be enumerated.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct Roles<R>
+where
+    R: BorshSerialize + BorshDeserialize + Ord,
+{
+    members: LookupMap<(R, Address), ()>,
+}
+
+impl<R> Roles<R>
+where
+    R: BorshSerialize + BorshDeserialize + Ord + Clone,
+{
+    /// Creates an empty set of roles. Uses `prefix` as a unique prefix for keys.
+    pub fn new(prefix: Vec<u8>) -> Self {
+        Self {
+            members: LookupMap::new(prefix),
+        }
+    }
+
+    /// Grants `role` to `address`. Returns `false` if `address` already had the role.
+    pub fn grant(&mut self, role: R, address: Address) -> bool {
+        self.members.insert((role, address), ()).is_none()
+    }
+
+    /// Revokes `role` from `address`. Returns `false` if `address` didn't have the role.
+    pub fn revoke(&mut self, role: R, address: Address) -> bool {
+        self.members.remove((role, address)).is_some()
+    }
+
+    /// Returns `true` if `address` has `role`.
+    pub fn has_role(&self, role: &R, address: &Address) -> bool {
+        self.members.contains_key(&(role.clone(), *address))
+    }
+
+    /// Panics unless [`crate::caller_address`] has `role`.
+    pub fn assert_role(&self, role: &R) {
+        if !self.has_role(role, &crate::caller_address()) {
+            crate::panic(ERR_MISSING_ROLE);
+        }
+    }
+
+    /// Writes the cached operations to the persistent storage.
+    pub fn flush(&mut self) {
+        self.members.flush();
+    }
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::*;
+    use super::*;
+
+    #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+    enum Role {
+        Admin,
+        Minter,
+    }
+
+    #[test]
+    fn test_grant_and_revoke() {
+        let alice = Address::from([1; 20]);
+        let bob = Address::from([2; 20]);
+        let mut roles: Roles<Role> = Roles::new(b"roles".to_vec());
+
+        assert!(roles.grant(Role::Admin, alice));
+        assert!(!roles.grant(Role::Admin, alice));
+        assert!(roles.grant(Role::Minter, bob));
+
+        assert!(roles.has_role(&Role::Admin, &alice));
+        assert!(!roles.has_role(&Role::Minter, &alice));
+        assert!(roles.has_role(&Role::Minter, &bob));
+        assert!(!roles.has_role(&Role::Admin, &bob));
+
+        assert!(roles.revoke(Role::Admin, alice));
+        assert!(!roles.revoke(Role::Admin, alice));
+        assert!(!roles.has_role(&Role::Admin, &alice));
+        drop(roles);
+
+        let roles: Roles<Role> = Roles::new(b"roles".to_vec());
+        assert!(!roles.has_role(&Role::Admin, &alice));
+        assert!(roles.has_role(&Role::Minter, &bob));
+    }
+
+    #[test]
+    fn test_string_roles() {
+        let alice = Address::from([1; 20]);
+        let mut roles: Roles<String> = Roles::new(b"roles".to_vec());
+
+        roles.grant("minter".to_string(), alice);
+        assert!(roles.has_role(&"minter".to_string(), &alice));
+        assert!(!roles.has_role(&"burner".to_string(), &alice));
+    }
+
+    #[test]
+    fn test_assert_role() {
+        let mut roles: Roles<Role> = Roles::new(b"roles".to_vec());
+        roles.grant(Role::Minter, crate::caller_address());
+        roles.assert_role(&Role::Minter);
+
+        let result = crate::utils::catch_panic(|| roles.assert_role(&Role::Admin));
+        assert!(result.is_err());
+        assert_eq!(get_mock_msgs().pop().unwrap(), ERR_MISSING_ROLE);
+
+        set_mock_caller_address(vec![7; 20]);
+        let result = crate::utils::catch_panic(|| roles.assert_role(&Role::Minter));
+        assert!(result.is_err());
+    }
+}
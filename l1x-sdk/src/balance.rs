@@ -0,0 +1,91 @@
+//! Overflow-checked [`Balance`] arithmetic.
+//!
+//! Plain `+` and `-` on `u128` wrap around in release builds, so an overflowing balance silently
+//! becomes a tiny one. Use these functions for token accounting instead:
+//!
+//! ```ignore
+//! use l1x_sdk::balance;
+//!
+//! pub fn transfer(&mut self, to: Address, amount: Balance) {
+//!     let from = l1x_sdk::caller_address();
+//!     let from_balance = self.balances.get(&from).copied().unwrap_or_default();
+//!     let to_balance = self.balances.get(&to).copied().unwrap_or_default();
+//!
+//!     self.balances.insert(from, balance::sub(from_balance, amount));
+//!     self.balances.insert(to, balance::add(to_balance, amount));
+//! }
+//! ```
+//!
+//! [`add`] and [`sub`] abort the call with a clear message, use [`checked_add`] and [`checked_sub`]
+//! to handle the failure yourself.
+use crate::types::Balance;
+
+const ERR_OVERFLOW: &str = "Balance overflow";
+const ERR_UNDERFLOW: &str = "Balance underflow";
+
+/// Returns `a + b`.
+///
+/// # Panic
+///
+/// Panics with "Balance overflow" if the sum exceeds [`Balance::MAX`]
+pub fn add(a: Balance, b: Balance) -> Balance {
+    checked_add(a, b).unwrap_or_else(|| crate::panic(ERR_OVERFLOW))
+}
+
+/// Returns `a - b`.
+///
+/// # Panic
+///
+/// Panics with "Balance underflow" if `b` is greater than `a`
+pub fn sub(a: Balance, b: Balance) -> Balance {
+    checked_sub(a, b).unwrap_or_else(|| crate::panic(ERR_UNDERFLOW))
+}
+
+/// Returns `a + b`, or `None` if the sum exceeds [`Balance::MAX`].
+pub fn checked_add(a: Balance, b: Balance) -> Option<Balance> {
+    a.checked_add(b)
+}
+
+/// Returns `a - b`, or `None` if `b` is greater than `a`.
+pub fn checked_sub(a: Balance, b: Balance) -> Option<Balance> {
+    a.checked_sub(b)
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_add_and_sub() {
+        assert_eq!(add(1, 2), 3);
+        assert_eq!(add(Balance::MAX - 1, 1), Balance::MAX);
+        assert_eq!(sub(3, 2), 1);
+        assert_eq!(sub(Balance::MAX, Balance::MAX), 0);
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert!(std::panic::catch_unwind(|| add(Balance::MAX, 1)).is_err());
+        assert_eq!(get_mock_msgs().pop().unwrap(), ERR_OVERFLOW);
+    }
+
+    #[test]
+    fn test_underflow() {
+        assert!(std::panic::catch_unwind(|| sub(1, 2)).is_err());
+        assert_eq!(get_mock_msgs().pop().unwrap(), ERR_UNDERFLOW);
+    }
+
+    #[test]
+    fn test_checked() {
+        assert_eq!(checked_add(1, 2), Some(3));
+        assert_eq!(checked_add(Balance::MAX, 0), Some(Balance::MAX));
+        assert_eq!(checked_add(Balance::MAX, 1), None);
+
+        assert_eq!(checked_sub(3, 2), Some(1));
+        assert_eq!(checked_sub(2, 2), Some(0));
+        assert_eq!(checked_sub(1, 2), None);
+    }
+}
@@ -1,8 +1,9 @@
 use borsh::BorshSerialize;
+use serde::Serialize;
 
 use crate::types;
 
-#[derive(BorshSerialize)]
+#[derive(BorshSerialize, Clone, Debug, PartialEq, Eq)]
 pub struct ContractCall {
     /// The target contract address
     pub contract_address: types::Address,
@@ -15,3 +16,216 @@ pub struct ContractCall {
     /// Gas limit for the call.
     pub gas_limit: types::Gas,
 }
+
+impl ContractCall {
+    /// Returns a builder for a call of `method_name` in the contract at `contract_address`.
+    pub fn builder(
+        contract_address: types::Address,
+        method_name: impl Into<String>,
+    ) -> ContractCallBuilder {
+        ContractCallBuilder {
+            call: ContractCall {
+                contract_address,
+                method_name: method_name.into(),
+                args: Vec::new(),
+                read_only: false,
+                gas_limit: 0,
+            },
+        }
+    }
+
+    /// Creates a call with `args` serialized as JSON.
+    ///
+    /// A shorthand for [`Self::builder`] with [`ContractCallBuilder::args_json`].
+    ///
+    /// # Panic
+    ///
+    /// Panics if serialization fails
+    pub fn new_json<A>(
+        contract_address: types::Address,
+        method_name: impl Into<String>,
+        args: &A,
+        read_only: bool,
+        gas_limit: types::Gas,
+    ) -> Self
+    where
+        A: Serialize + ?Sized,
+    {
+        Self::builder(contract_address, method_name)
+            .args_json(args)
+            .read_only(read_only)
+            .gas_limit(gas_limit)
+            .build()
+    }
+
+    /// Creates a call with `args` serialized with Borsh.
+    ///
+    /// A shorthand for [`Self::builder`] with [`ContractCallBuilder::args_borsh`].
+    ///
+    /// # Panic
+    ///
+    /// Panics if serialization fails
+    pub fn new_borsh<A>(
+        contract_address: types::Address,
+        method_name: impl Into<String>,
+        args: &A,
+        read_only: bool,
+        gas_limit: types::Gas,
+    ) -> Self
+    where
+        A: BorshSerialize + ?Sized,
+    {
+        Self::builder(contract_address, method_name)
+            .args_borsh(args)
+            .read_only(read_only)
+            .gas_limit(gas_limit)
+            .build()
+    }
+}
+
+/// A builder for [`ContractCall`], created by [`ContractCall::builder`].
+///
+/// By default the call has empty arguments, isn't read-only and has a gas limit of `0`, so the gas
+/// limit is expected to be set with [`Self::gas_limit`].
+pub struct ContractCallBuilder {
+    call: ContractCall,
+}
+
+impl ContractCallBuilder {
+    /// Sets the arguments to `args` serialized as JSON.
+    ///
+    /// # Panic
+    ///
+    /// Panics if serialization fails
+    pub fn args_json<T>(mut self, args: &T) -> Self
+    where
+        T: Serialize + ?Sized,
+    {
+        self.call.args = serde_json::to_vec(args)
+            .unwrap_or_else(|_| crate::panic("Cannot serialize the call arguments using JSON"));
+        self
+    }
+
+    /// Sets the arguments to `args` serialized with Borsh.
+    ///
+    /// # Panic
+    ///
+    /// Panics if serialization fails
+    pub fn args_borsh<T>(mut self, args: &T) -> Self
+    where
+        T: BorshSerialize + ?Sized,
+    {
+        self.call.args = args
+            .try_to_vec()
+            .unwrap_or_else(|_| crate::panic("Cannot serialize the call arguments using Borsh"));
+        self
+    }
+
+    /// Sets whether the call is read-only.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.call.read_only = read_only;
+        self
+    }
+
+    /// Sets the gas limit for the call.
+    pub fn gas_limit(mut self, gas_limit: types::Gas) -> Self {
+        self.call.gas_limit = gas_limit;
+        self
+    }
+
+    /// Returns the configured [`ContractCall`].
+    pub fn build(self) -> ContractCall {
+        self.call
+    }
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Serialize, BorshSerialize)]
+    struct TransferArgs {
+        to: String,
+        amount: u64,
+    }
+
+    fn address() -> types::Address {
+        types::Address::try_from("a11ce00000000000000000000000000000000000").unwrap()
+    }
+
+    #[test]
+    fn test_builder_defaults() {
+        let call = ContractCall::builder(address(), "ping").build();
+
+        assert_eq!(call.contract_address, address());
+        assert_eq!(call.method_name, "ping");
+        assert!(call.args.is_empty());
+        assert!(!call.read_only);
+        assert_eq!(call.gas_limit, 0);
+    }
+
+    #[test]
+    fn test_builder_args_json() {
+        let args = TransferArgs {
+            to: "bob".to_string(),
+            amount: 10,
+        };
+        let call = ContractCall::builder(address(), "transfer")
+            .args_json(&args)
+            .read_only(true)
+            .gas_limit(500)
+            .build();
+
+        assert_eq!(call.args, br#"{"to":"bob","amount":10}"#);
+        assert!(call.read_only);
+        assert_eq!(call.gas_limit, 500);
+    }
+
+    #[test]
+    fn test_builder_args_borsh() {
+        let args = TransferArgs {
+            to: "bob".to_string(),
+            amount: 10,
+        };
+        let call = ContractCall::builder(address(), "transfer")
+            .args_borsh(&args)
+            .build();
+
+        assert_eq!(call.args, args.try_to_vec().unwrap());
+    }
+
+    #[test]
+    fn test_new_json() {
+        let args = TransferArgs {
+            to: "bob".to_string(),
+            amount: 10,
+        };
+        let call = ContractCall::new_json(address(), "transfer", &args, true, 500);
+
+        assert_eq!(call.contract_address, address());
+        assert_eq!(call.method_name, "transfer");
+        assert_eq!(call.args, br#"{"to":"bob","amount":10}"#);
+        assert!(call.read_only);
+        assert_eq!(call.gas_limit, 500);
+    }
+
+    #[test]
+    fn test_new_borsh() {
+        let args = TransferArgs {
+            to: "bob".to_string(),
+            amount: 10,
+        };
+        let call = ContractCall::new_borsh(address(), "transfer", &args, false, 500);
+
+        // `to` as a length-prefixed string, then `amount` as a little-endian u64
+        let mut expected = vec![3, 0, 0, 0];
+        expected.extend_from_slice(b"bob");
+        expected.extend_from_slice(&10u64.to_le_bytes());
+        assert_eq!(call.args, expected);
+        assert!(!call.read_only);
+        assert_eq!(call.gas_limit, 500);
+    }
+}
@@ -1,3 +1,3 @@
 mod contract_call;
 
-pub use contract_call::ContractCall;
+pub use contract_call::{ContractCall, ContractCallBuilder};
@@ -0,0 +1,346 @@
+//! Tests for the code generated by [`crate::contract`].
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::contract;
+use crate::tests::*;
+
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+pub struct Counter {
+    value: u64,
+}
+
+#[contract(abi, default_state)]
+impl Counter {
+    pub fn increment(&mut self) {
+        self.value += 1;
+    }
+
+    pub fn add(&mut self, amount: u64) {
+        self.value += amount;
+    }
+
+    #[view]
+    pub fn get(&self) -> u64 {
+        self.value
+    }
+
+    #[view]
+    #[method_name = "get_v2"]
+    pub fn get_value(&self) -> u64 {
+        self.value
+    }
+
+    /// Exported, its wrapper doesn't clash with the `double` function below
+    pub fn double(&self) -> u64 {
+        self.value * 2
+    }
+
+    #[private]
+    pub fn half(&self) -> u64 {
+        self.value / 2
+    }
+
+    #[export]
+    fn reset(&mut self) {
+        self.value = 0;
+    }
+
+    pub fn echo(msg: String) -> String {
+        msg
+    }
+
+    pub fn checked_sub(&mut self, amount: u64) -> Result<u64, String> {
+        self.value = self
+            .value
+            .checked_sub(amount)
+            .ok_or_else(|| "Insufficient value".to_string())?;
+        Ok(self.value)
+    }
+}
+
+fn double() {}
+
+fn call<I>(method: extern "C" fn(), input: &I) -> Vec<u8>
+where
+    I: AsRef<[u8]> + ?Sized,
+{
+    set_mock_input(input.as_ref().to_vec());
+    method();
+    get_mock_output()
+}
+
+#[test]
+fn test_receiver_methods() {
+    assert_eq!(call(__l1x_export_get, "{}"), b"0");
+
+    call(__l1x_export_increment, "{}");
+    call(__l1x_export_increment, "{}");
+    call(__l1x_export_add, r#"{"amount": 40}"#);
+
+    assert_eq!(call(__l1x_export_get, "{}"), b"42");
+    assert_eq!(
+        crate::state_read::<Counter>().map(|counter| counter.value),
+        Some(42)
+    );
+}
+
+#[test]
+fn test_method_name() {
+    call(__l1x_export_add, r#"{"amount": 5}"#);
+
+    assert_eq!(call(__l1x_export_get_v2, "{}"), b"5");
+    assert_eq!(crate::state_read::<Counter>().unwrap().get_value(), 5);
+}
+
+#[test]
+fn test_private_and_export() {
+    call(__l1x_export_add, r#"{"amount": 4}"#);
+    assert_eq!(call(__l1x_export_double, "{}"), b"8");
+    assert_eq!(crate::state_read::<Counter>().unwrap().half(), 2);
+    double();
+
+    call(__l1x_export_reset, "{}");
+    assert_eq!(call(__l1x_export_get, "{}"), b"0");
+}
+
+/// Runs `f` and returns the message it panicked with through [`crate::panic`].
+///
+/// The generated wrappers are `extern "C"`, so a panic inside them aborts the test process. The
+/// failure paths are therefore tested through the helpers the wrappers call.
+fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> String {
+    assert!(std::panic::catch_unwind(f).is_err());
+    get_mock_msgs().pop().unwrap()
+}
+
+#[test]
+fn test_input_error_messages() {
+    #[derive(serde::Deserialize, BorshDeserialize)]
+    struct Input {
+        #[allow(dead_code)]
+        amount: u64,
+    }
+
+    set_mock_input(br#"{"amount": "ten"}"#.to_vec());
+    let message = panic_message(|| {
+        crate::__method_input_json::<Input>("add");
+    });
+    assert!(message.starts_with("Failed to deserialize input of `add` from JSON: invalid type"));
+
+    set_mock_input(vec![1]);
+    let message = panic_message(|| {
+        crate::__method_input_borsh::<Input>("add");
+    });
+    assert!(message.starts_with("Failed to deserialize input of `add` from Borsh: "));
+
+    clear_mock_io();
+    let message = panic_message(|| {
+        crate::__method_input_json::<Input>("add");
+    });
+    assert_eq!(message, "Expected input since method `add` has arguments.");
+}
+
+#[test]
+fn test_output_error_message() {
+    let mut map = std::collections::HashMap::new();
+    map.insert(vec![1u8], 1u64);
+
+    let message = panic_message(|| crate::__method_output_json("balances", &map));
+    assert_eq!(
+        message,
+        "Failed to serialize the return value of `balances` using JSON: key must be a string"
+    );
+}
+
+#[test]
+fn test_shared_receiver_does_not_write_state() {
+    call(__l1x_export_get, "{}");
+    assert!(crate::storage_read(crate::STATE_KEY).is_none());
+}
+
+#[test]
+fn test_view_methods_do_not_write() {
+    call(__l1x_export_add, r#"{"amount": 1}"#);
+    let writes = get_mock_storage_writes();
+
+    assert_eq!(call(__l1x_export_get, "{}"), b"1");
+    assert_eq!(call(__l1x_export_get_v2, "{}"), b"1");
+
+    assert_eq!(get_mock_storage_writes(), writes);
+}
+
+#[test]
+fn test_reentrancy_guard_released_after_call() {
+    let writes = get_mock_storage_writes();
+    call(__l1x_export_increment, "{}");
+
+    // The guard and the state
+    assert_eq!(get_mock_storage_writes(), writes + 2);
+    assert!(crate::storage_read(&crate::reentrancy_guard_key()).is_none());
+}
+
+#[test]
+fn test_read_only_call_does_not_set_guard() {
+    set_mock_write_perm(false);
+    call(__l1x_export_echo, r#"{"msg": "hello"}"#);
+
+    assert!(crate::storage_read(&crate::reentrancy_guard_key()).is_none());
+}
+
+#[test]
+fn test_associated_function() {
+    assert_eq!(
+        call(__l1x_export_echo, r#"{"msg": "hello"}"#),
+        br#""hello""#
+    );
+}
+
+#[test]
+fn test_result_ok_serializes_value() {
+    call(__l1x_export_add, r#"{"amount": 5}"#);
+    assert_eq!(call(__l1x_export_checked_sub, r#"{"amount": 3}"#), b"2");
+}
+
+#[test]
+fn test_abi() {
+    let abi: serde_json::Value = serde_json::from_str(&__contract_abi()).unwrap();
+    assert_eq!(abi["input"], "json");
+
+    let methods = abi["methods"].as_array().unwrap();
+    assert_eq!(methods.len(), 8);
+    assert!(methods.iter().any(|method| method["name"] == "reset"));
+    assert!(methods.iter().any(|method| method["name"] == "double"));
+    assert!(!methods.iter().any(|method| method["name"] == "half"));
+    assert!(methods.iter().any(|method| method["name"] == "get_v2"));
+    assert!(!methods.iter().any(|method| method["name"] == "get_value"));
+
+    let add = methods
+        .iter()
+        .find(|method| method["name"] == "add")
+        .unwrap();
+    assert_eq!(
+        add,
+        &serde_json::json!({
+            "name": "add",
+            "view": false,
+            "receiver": "&mut self",
+            "args": [{"name": "amount", "type": "u64"}],
+            "returns": null,
+        })
+    );
+
+    let checked_sub = methods
+        .iter()
+        .find(|method| method["name"] == "checked_sub")
+        .unwrap();
+    assert_eq!(checked_sub["returns"], "Result<u64, String>");
+
+    let get = methods
+        .iter()
+        .find(|method| method["name"] == "get")
+        .unwrap();
+    assert_eq!(get["view"], true);
+    assert_eq!(get["receiver"], "&self");
+}
+
+#[test]
+fn test_uninitialized_state_message() {
+    let message = panic_message(|| {
+        crate::__contract_state_read::<Counter>("get");
+    });
+    assert_eq!(
+        message,
+        "Method `get` needs the contract state, but it has never been written."
+    );
+}
+
+mod initialized_state {
+    use borsh::{BorshDeserialize, BorshSerialize};
+
+    use super::call;
+    use crate::contract;
+
+    /// Doesn't implement `Default`
+    #[derive(BorshSerialize, BorshDeserialize)]
+    pub struct Token {
+        supply: u64,
+    }
+
+    #[contract]
+    impl Token {
+        pub fn init(supply: u64) {
+            crate::state_write(&Token { supply });
+        }
+
+        pub fn burn(&mut self, amount: u64) {
+            self.supply -= amount;
+        }
+
+        #[view]
+        pub fn supply(&self) -> u64 {
+            self.supply
+        }
+    }
+
+    #[test]
+    fn test_initialized_state() {
+        call(__l1x_export_init, r#"{"supply": 100}"#);
+        call(__l1x_export_burn, r#"{"amount": 30}"#);
+
+        assert_eq!(call(__l1x_export_supply, "{}"), b"70");
+        assert_eq!(crate::state_read::<Token>().unwrap().supply, 70);
+    }
+}
+
+mod borsh_input {
+    use borsh::{BorshDeserialize, BorshSerialize};
+
+    use super::call;
+    use crate::contract;
+
+    /// Doesn't implement `serde::Deserialize`
+    #[derive(BorshSerialize, BorshDeserialize)]
+    pub struct Point {
+        x: u32,
+        y: u32,
+    }
+
+    pub struct Geometry;
+
+    #[contract(input = "borsh")]
+    impl Geometry {
+        pub fn sum(point: Point, scale: u32) -> u32 {
+            (point.x + point.y) * scale
+        }
+    }
+
+    #[test]
+    fn test_borsh_input() {
+        let input = (Point { x: 1, y: 2 }, 10u32).try_to_vec().unwrap();
+        assert_eq!(call(__l1x_export_sum, &input), b"30");
+    }
+}
+
+mod no_reentrancy_guard {
+    use super::call;
+    use crate::contract;
+    use crate::tests::*;
+
+    pub struct Unguarded;
+
+    #[contract(no_reentrancy_guard)]
+    impl Unguarded {
+        pub fn ping() -> String {
+            "pong".to_string()
+        }
+    }
+
+    #[test]
+    fn test_no_reentrancy_guard() {
+        // Simulates a call that re-enters the contract
+        crate::storage_write(&crate::reentrancy_guard_key(), b"");
+        let writes = get_mock_storage_writes();
+
+        assert_eq!(call(__l1x_export_ping, "{}"), br#""pong""#);
+        assert_eq!(get_mock_storage_writes(), writes);
+    }
+}
@@ -0,0 +1,188 @@
+//! Hash functions and signature recovery.
+//!
+//! The VM doesn't provide cryptographic host functions, so these are implemented in pure Rust. Only
+//! the functions a contract actually calls end up in the compiled wasm.
+//!
+//! Requires the `crypto` feature.
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use sha2::Digest;
+use tiny_keccak::Hasher;
+
+use crate::types::Address;
+
+/// Returns the Keccak-256 hash of `data`, as used by Ethereum (not the standardized SHA3-256).
+///
+/// # Examples
+/// ```
+/// use l1x_sdk::crypto::keccak256;
+///
+/// assert_eq!(
+///     hex::encode(keccak256(b"")),
+///     "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+/// );
+/// ```
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = tiny_keccak::Keccak::v256();
+    hasher.update(data);
+    let mut output = [0u8; 32];
+    hasher.finalize(&mut output);
+    output
+}
+
+/// Returns the SHA-256 hash of `data`.
+///
+/// # Examples
+/// ```
+/// use l1x_sdk::crypto::sha256;
+///
+/// assert_eq!(
+///     hex::encode(sha256(b"")),
+///     "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+/// );
+/// ```
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    sha2::Sha256::digest(data).into()
+}
+
+/// Recovers the address that signed `hash` with the secp256k1 signature `sig`, like the EVM `ecrecover`
+/// precompile.
+///
+/// `sig` is `r ++ s ++ v`, where the recovery id `v` is either `0`/`1` or `27`/`28`. The address is the
+/// last 20 bytes of the [`keccak256`] hash of the recovered uncompressed public key.
+///
+/// Like the precompile, both the low-S and the high-S form of a signature are accepted and recover
+/// the same address. Contracts that use signatures as unique identifiers have to deal with this
+/// malleability themselves, e.g. by tracking nonces instead of signatures.
+///
+/// Returns `None` if the signature is invalid.
+pub fn ecrecover(hash: &[u8; 32], sig: &[u8; 65]) -> Option<Address> {
+    let y_is_odd = match sig[64] {
+        0 | 27 => false,
+        1 | 28 => true,
+        _ => return None,
+    };
+    let signature = Signature::from_slice(&sig[..64]).ok()?;
+    // `k256` only recovers from low-S signatures. Negating `s` also negates the recovered point, so
+    // the parity of the recovery id flips
+    let (signature, y_is_odd) = match signature.normalize_s() {
+        Some(normalized) => (normalized, !y_is_odd),
+        None => (signature, y_is_odd),
+    };
+    let recovery_id = RecoveryId::new(y_is_odd, false);
+    let public_key = VerifyingKey::recover_from_prehash(hash, &signature, recovery_id).ok()?;
+
+    let public_key = public_key.to_encoded_point(false);
+    let hash = keccak256(&public_key.as_bytes()[1..]);
+    Address::try_from(&hash[12..]).ok()
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keccak256() {
+        assert_eq!(
+            hex::encode(keccak256(b"")),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(
+            hex::encode(keccak256(b"hello world")),
+            "47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad"
+        );
+        // ERC-20 `transfer(address,uint256)` selector
+        assert_eq!(
+            hex::encode(&keccak256(b"transfer(address,uint256)")[..4]),
+            "a9059cbb"
+        );
+    }
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(
+            hex::encode(sha256(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex::encode(sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex::encode(sha256(b"hello world")),
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        );
+    }
+
+    // Signed by the private key `1`
+    const HASH: &str = "3d09736a5122d9e89b36f08fe97a0ca37af79a7630da518bf048e76a5f37cefb";
+    const SIGNATURE: &str = "96f9486bfa72e7a43c8d3f9de8ecab566f37dbcb4ed64904b4ebf01f7de7bb8758a3df7e0816fc4b9c5499ba8e5346da1ca3021bed74a00cea8aa08fc17a7c731b";
+    const SIGNER: &str = "7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+
+    fn vector() -> ([u8; 32], [u8; 65]) {
+        let hash = hex::decode(HASH).unwrap().try_into().unwrap();
+        let sig = hex::decode(SIGNATURE).unwrap().try_into().unwrap();
+        (hash, sig)
+    }
+
+    #[test]
+    fn test_ecrecover() {
+        let (hash, mut sig) = vector();
+        assert_eq!(hash, keccak256(b"hello l1x"));
+
+        let signer = Address::try_from(SIGNER).unwrap();
+        assert_eq!(ecrecover(&hash, &sig), Some(signer));
+
+        // `v` without the 27 offset
+        sig[64] -= 27;
+        assert_eq!(ecrecover(&hash, &sig), Some(signer));
+    }
+
+    #[test]
+    fn test_ecrecover_invalid() {
+        let (hash, sig) = vector();
+        let signer = Address::try_from(SIGNER).unwrap();
+
+        // Another message recovers another address
+        assert_ne!(ecrecover(&keccak256(b"other"), &sig), Some(signer));
+
+        let mut bad_v = sig;
+        bad_v[64] = 29;
+        assert_eq!(ecrecover(&hash, &bad_v), None);
+
+        let mut zero_r = sig;
+        zero_r[..32].fill(0);
+        assert_eq!(ecrecover(&hash, &zero_r), None);
+
+        assert_eq!(ecrecover(&hash, &[0u8; 65]), None);
+    }
+
+    #[test]
+    fn test_ecrecover_high_s() {
+        let (hash, sig) = vector();
+        let signer = Address::try_from(SIGNER).unwrap();
+
+        // Replace `s` with `n - s` and flip `v`, the EVM recovers the same signer
+        let (r, s) = Signature::from_slice(&sig[..64]).unwrap().split_scalars();
+        let high_s = Signature::from_scalars(r, -s).unwrap();
+        assert!(high_s.normalize_s().is_some());
+
+        let mut malleated = [0u8; 65];
+        malleated[..64].copy_from_slice(&high_s.to_bytes());
+        malleated[64] = if sig[64] == 27 { 28 } else { 27 };
+        assert_eq!(ecrecover(&hash, &malleated), Some(signer));
+
+        malleated[64] -= 27;
+        assert_eq!(ecrecover(&hash, &malleated), Some(signer));
+    }
+
+    #[test]
+    fn test_ecrecover_rejects_x_reduced_v() {
+        let (hash, mut sig) = vector();
+        for v in [2, 3, 26, 29, 255] {
+            sig[64] = v;
+            assert_eq!(ecrecover(&hash, &sig), None);
+        }
+    }
+}
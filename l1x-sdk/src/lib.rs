@@ -1,25 +1,61 @@
-use borsh::BorshSerialize;
-pub use l1x_sdk_macros::contract;
+use borsh::{BorshDeserialize, BorshSerialize};
+pub use l1x_sdk_macros::{contract, Event};
 pub use l1x_sys as sys;
+use serde::de::DeserializeOwned;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::ops::ControlFlow;
+#[cfg(not(test))]
 use std::panic as std_panic;
+use std::rc::Rc;
 use types::{Address, Balance, BlockHash, BlockNumber, Gas, TimeStamp};
 
+pub mod access;
+pub mod balance;
 pub mod contract_interaction;
+#[cfg(feature = "crypto")]
+pub mod crypto;
+pub mod log;
+pub mod migrate;
+pub mod pausable;
+#[cfg(feature = "crypto")]
+pub mod random;
+pub mod registers;
+pub mod state;
 pub mod store;
+pub mod timelock;
 pub mod types;
 use contract_interaction::ContractCall;
 pub mod utils;
+
+#[cfg(test)]
+mod contract_tests;
+#[cfg(test)]
+mod testing;
+
+// Lets `#[contract]` refer to `l1x_sdk` from within this crate
+extern crate self as l1x_sdk;
 pub(crate) use crate::utils::*;
 
-const EVICTED_REGISTER: u64 = std::u64::MAX - 1;
-const ATOMIC_OP_REGISTER: u64 = std::u64::MAX - 2;
+use registers::{read as read_register, ATOMIC_OP_REGISTER, EVICTED_REGISTER};
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum TransferError {
     TransferFailed,
     InsufficientFunds,
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReentrancyError {
+    AlreadyEntered,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EventError {
+    SerializationFailed,
+    EmitFailed,
+}
+
 macro_rules! try_method_into_register {
     ( $method:ident ) => {{
         unsafe { l1x_sys::$method(ATOMIC_OP_REGISTER) };
@@ -33,44 +69,21 @@ macro_rules! method_into_register {
     }};
 }
 
-/// Returns the size of the register. If register is not used returns `None`.
-fn register_len(register_id: u64) -> Option<u64> {
-    let len = unsafe { l1x_sys::register_len(register_id) };
-    if len == std::u64::MAX {
-        None
-    } else {
-        Some(len)
-    }
-}
-
-/// Reads the content of the `register_id`. If register is not used returns `None`.
-fn read_register(register_id: u64) -> Option<Vec<u8>> {
-    let len: usize = register_len(register_id)?
-        .try_into()
-        .unwrap_or_else(|_| abort());
-
-    let mut buffer = Vec::with_capacity(len);
-
-    unsafe {
-        l1x_sys::read_register(register_id, buffer.as_mut_ptr() as u64);
-
-        buffer.set_len(len);
-    }
-    Some(buffer)
-}
-
 fn expect_register<T>(option: Option<T>) -> T {
     option.unwrap_or_else(|| abort())
 }
 
 /// Implements panic hook that converts `PanicInfo` into a string and provides it through the
 /// blockchain interface.
+#[cfg(not(test))]
 fn panic_hook_impl(info: &std_panic::PanicInfo) {
     panic(&info.to_string());
 }
 
 /// Setups panic hook to expose error info to the blockchain.
 pub fn setup_panic_hook() {
+    // The mocked `panic` panics itself, so the hook would recurse
+    #[cfg(not(test))]
     std_panic::set_hook(Box::new(panic_hook_impl));
 }
 
@@ -107,6 +120,22 @@ pub fn input() -> Option<Vec<u8>> {
     try_method_into_register!(input)
 }
 
+/// The length of the input to the contract call in bytes. If input is not provided returns `None`.
+///
+/// Unlike [`input`], doesn't copy the input into the contract memory, e.g. to reject oversized
+/// payloads before reading them.
+pub fn input_len() -> Option<usize> {
+    #[cfg(test)]
+    {
+        return tests::input_len();
+    }
+    #[cfg(not(test))]
+    {
+        unsafe { l1x_sys::input(ATOMIC_OP_REGISTER) };
+        registers::len(ATOMIC_OP_REGISTER).map(|len| len.try_into().unwrap_or_else(|_| abort()))
+    }
+}
+
 /// Writes `data` to 'output' register
 pub fn output(data: &[u8]) {
     #[cfg(test)]
@@ -119,6 +148,147 @@ pub fn output(data: &[u8]) {
     }
 }
 
+/// Deserializes the input to the contract call from JSON. If input is not provided returns `None`.
+///
+/// # Panic
+///
+/// Panics if the input can't be deserialized as `T`
+pub fn input_json<T>() -> Option<T>
+where
+    T: DeserializeOwned,
+{
+    input().map(|bytes| {
+        serde_json::from_slice(&bytes)
+            .unwrap_or_else(|e| panic(&format!("Cannot deserialize the input using JSON: {}", e)))
+    })
+}
+
+/// Deserializes the input to the contract call with Borsh. If input is not provided returns `None`.
+///
+/// # Panic
+///
+/// Panics if the input can't be deserialized as `T`
+pub fn input_borsh<T>() -> Option<T>
+where
+    T: BorshDeserialize,
+{
+    input().map(|bytes| {
+        T::try_from_slice(&bytes)
+            .unwrap_or_else(|e| panic(&format!("Cannot deserialize the input using Borsh: {}", e)))
+    })
+}
+
+/// Deserializes the positional arguments of the contract call with Borsh.
+///
+/// Borsh encodes a tuple as its fields one after another, so several arguments can be read at once:
+///
+/// ```ignore
+/// let (amount, to): (u64, Address) = l1x_sdk::decode_args();
+/// ```
+///
+/// # Panic
+///
+/// Panics if input is not provided or can't be deserialized as `T`
+pub fn decode_args<T>() -> T
+where
+    T: BorshDeserialize,
+{
+    input_borsh().unwrap_or_else(|| panic("Expected the arguments as input"))
+}
+
+/// Serializes `value` to JSON and writes it with [`output`]
+pub fn output_json<T>(value: &T)
+where
+    T: serde::Serialize + ?Sized,
+{
+    let data = serde_json::to_vec(value)
+        .unwrap_or_else(|_| panic("Failed to serialize the return value using JSON."));
+    output(&data)
+}
+
+/// Serializes `value` with Borsh and writes it with [`output`]
+pub fn output_borsh<T>(value: &T)
+where
+    T: BorshSerialize + ?Sized,
+{
+    let data = value
+        .try_to_vec()
+        .unwrap_or_else(|_| panic("Failed to serialize the return value using Borsh."));
+    output(&data)
+}
+
+/// Reads the arguments of `method` from JSON. Used by the code generated by [`contract`].
+#[doc(hidden)]
+pub fn __method_input_json<T>(method: &str) -> T
+where
+    T: DeserializeOwned,
+{
+    let input = method_input(method);
+    serde_json::from_slice(&input).unwrap_or_else(|e| {
+        panic(&format!(
+            "Failed to deserialize input of `{}` from JSON: {}",
+            method, e
+        ))
+    })
+}
+
+/// Reads the arguments of `method` with Borsh. Used by the code generated by [`contract`].
+#[doc(hidden)]
+pub fn __method_input_borsh<T>(method: &str) -> T
+where
+    T: BorshDeserialize,
+{
+    let input = method_input(method);
+    T::try_from_slice(&input).unwrap_or_else(|e| {
+        panic(&format!(
+            "Failed to deserialize input of `{}` from Borsh: {}",
+            method, e
+        ))
+    })
+}
+
+fn method_input(method: &str) -> Vec<u8> {
+    input().unwrap_or_else(|| {
+        panic(&format!(
+            "Expected input since method `{}` has arguments.",
+            method
+        ))
+    })
+}
+
+/// Writes the return value of `method` as JSON. Used by the code generated by [`contract`].
+#[doc(hidden)]
+pub fn __method_output_json<T>(method: &str, value: &T)
+where
+    T: serde::Serialize + ?Sized,
+{
+    let data = serde_json::to_vec(value).unwrap_or_else(|e| {
+        panic(&format!(
+            "Failed to serialize the return value of `{}` using JSON: {}",
+            method, e
+        ))
+    });
+    output(&data)
+}
+
+/// Reads the contract state for `method`. Used by the code generated by [`contract`].
+///
+/// # Panic
+///
+/// Panics if the state has never been written
+#[doc(hidden)]
+pub fn __contract_state_read<T>(method: &str) -> T
+where
+    T: BorshDeserialize,
+{
+    state_read().unwrap_or_else(|| {
+        panic(&format!(
+            "Method `{}` needs the contract state, but it has never been written.",
+            method
+        ))
+    })
+}
+
 pub fn msg(message: &str) {
     #[cfg(test)]
     {
@@ -133,12 +303,38 @@ pub fn msg(message: &str) {
     }
 }
 
+/// The prefix of the messages emitted by [`log_json`].
+pub const EVENT_JSON_PREFIX: &str = "EVENT_JSON:";
+
+/// Emits `value` serialized as JSON with [`msg`], prefixed with [`EVENT_JSON_PREFIX`], e.g.
+/// `EVENT_JSON:{"kind":"deposit","amount":"10"}`.
+///
+/// Indexers can pick these messages out of the logs by the prefix and parse the rest as JSON. The
+/// data is only visible off-chain, contracts can't read it back.
+///
+/// # Panics
+///
+/// Panics if serialization fails
+pub fn log_json<T>(value: &T)
+where
+    T: serde::Serialize + ?Sized,
+{
+    let json = serde_json::to_string(value).unwrap_or_else(|e| {
+        panic(&format!(
+            "Failed to serialize the log event using JSON: {}",
+            e
+        ))
+    });
+    msg(&format!("{}{}", EVENT_JSON_PREFIX, json));
+}
+
 /// Writes key-value into storage.
 ///
 /// If the the storage did not have this key present, `false` is returned.
 ///
 /// If the map did have this key present, the value is updated, and `true` is returned.
 pub fn storage_write(key: &[u8], value: &[u8]) -> bool {
+    invalidate_cached_read(key);
     #[cfg(test)]
     {
         return tests::storage_write(key, value);
@@ -159,10 +355,21 @@ pub fn storage_write(key: &[u8], value: &[u8]) -> bool {
     }
 }
 
+/// Writes all the given key-value pairs into storage.
+///
+/// A convenience loop that issues one [`storage_write`] per pair, in order. The VM doesn't provide a
+/// batched host function, so this isn't cheaper than calling [`storage_write`] directly.
+pub fn storage_write_many(entries: &[(Vec<u8>, Vec<u8>)]) {
+    for (key, value) in entries {
+        storage_write(key, value);
+    }
+}
+
 /// Removes the value stored under the given key.
 ///
 /// If key-value existed returns `true`, otherwise `false`.
 pub fn storage_remove(key: &[u8]) -> bool {
+    invalidate_cached_read(key);
     #[cfg(test)]
     {
         return tests::storage_remove(key);
@@ -193,8 +400,58 @@ pub fn storage_read(key: &[u8]) -> Option<Vec<u8>> {
     }
 }
 
+type ReadCache = BTreeMap<Vec<u8>, Option<Rc<Vec<u8>>>>;
+
+thread_local! {
+    static STORAGE_READ_CACHE: RefCell<ReadCache> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// Reads the value stored under the given key like [`storage_read`], but only calls the host the
+/// first time the key is read.
+///
+/// The value is cached in memory until [`storage_write`] or [`storage_remove`] is called for the
+/// same key. The contract memory doesn't outlive a call, so neither does the cache: the next call
+/// reads the value from the storage again.
+pub fn storage_read_cached(key: &[u8]) -> Option<Rc<Vec<u8>>> {
+    if let Some(value) = STORAGE_READ_CACHE.with(|cache| cache.borrow().get(key).cloned()) {
+        return value;
+    }
+    let value = storage_read(key).map(Rc::new);
+    STORAGE_READ_CACHE.with(|cache| cache.borrow_mut().insert(key.to_vec(), value.clone()));
+    value
+}
+
+fn invalidate_cached_read(key: &[u8]) {
+    STORAGE_READ_CACHE.with(|cache| cache.borrow_mut().remove(key));
+}
+
+/// Returns `true` if the storage has a value under the given key.
+///
+/// The VM has no dedicated host function for this, so the host still loads the value into a register,
+/// but the value is never copied into the contract memory or deserialized. Prefer this over
+/// [`storage_read`] when only the presence of the key matters.
+pub fn storage_has_key(key: &[u8]) -> bool {
+    #[cfg(test)]
+    {
+        return tests::storage_has_key(key);
+    }
+
+    #[cfg(not(test))]
+    match unsafe { sys::storage_read(key.as_ptr() as _, key.len() as _, ATOMIC_OP_REGISTER) } {
+        0 => false,
+        1 => true,
+        _ => abort(),
+    }
+}
+
 /// Returns `true` if the contract has write permissions and `false` if it doesn't.
 pub fn storage_write_perm() -> bool {
+    #[cfg(test)]
+    {
+        return tests::storage_write_perm();
+    }
+
+    #[cfg(not(test))]
     match unsafe { sys::storage_write_perm() } {
         0 => false,
         1 => true,
@@ -202,6 +459,124 @@ pub fn storage_write_perm() -> bool {
     }
 }
 
+/// The storage key under which [`state_write`] keeps the contract state.
+///
+/// The methods generated by [`contract`] read the state from this key and panic if it has never been
+/// written, so write the initial state here first, e.g. from an exported constructor. With
+/// `#[contract(default_state)]` they fall back to [`Default`] instead.
+pub const STATE_KEY: &[u8] = b"STATE";
+
+/// Reads the contract state stored under [`STATE_KEY`].
+///
+/// If the state has never been written, returns `None`.
+///
+/// # Panic
+///
+/// Panics if the stored state can't be deserialized as `T`
+pub fn state_read<T>() -> Option<T>
+where
+    T: BorshDeserialize,
+{
+    state::load(STATE_KEY)
+}
+
+/// Writes the contract state under [`STATE_KEY`].
+///
+/// # Panic
+///
+/// Panics if serialization fails
+pub fn state_write<T>(state: &T)
+where
+    T: BorshSerialize,
+{
+    state::save(STATE_KEY, state)
+}
+
+/// The prefix of the reentrancy guard key, see [`reentrancy_guard_key`].
+pub const REENTRANCY_GUARD_KEY_PREFIX: &[u8] = b"__REENTRANCY_GUARD__";
+
+/// Returns the storage key of the reentrancy guard: [`REENTRANCY_GUARD_KEY_PREFIX`] followed by the
+/// address of the current contract instance.
+pub fn reentrancy_guard_key() -> Vec<u8> {
+    let mut key = REENTRANCY_GUARD_KEY_PREFIX.to_vec();
+    key.extend_from_slice(contract_instance_address().as_bytes());
+    key
+}
+
+/// Acquires the reentrancy guard of the current contract instance. Called by `#[contract]` wrappers
+/// before the contract method runs.
+///
+/// The policy is "no reentrancy into the same contract instance":
+///
+/// * If `write_perm` is `true`, the guard is set for the duration of the call. Any call that enters the same
+///   contract instance before the guard is released fails, even if it targets a different method. For example,
+///   if `A::deposit` calls contract `B` and `B` calls back `A::balance`, the call to `A::balance` is rejected.
+/// * If `write_perm` is `false`, the guard is only checked, so a read-only call can't observe the contract
+///   in the middle of another call either.
+///
+/// Contracts that manage reentrancy themselves can opt out with `#[contract(no_reentrancy_guard)]`.
+///
+/// # Panic
+///
+/// Panics with "Found a cross-contract call loop" if the guard is already set
+pub fn reentrancy_guard_enter(write_perm: bool) {
+    let key = reentrancy_guard_key();
+    let is_set = if write_perm {
+        storage_write(&key, b"")
+    } else {
+        storage_read(&key).is_some()
+    };
+    if is_set {
+        panic("Found a cross-contract call loop");
+    }
+}
+
+/// Releases the reentrancy guard acquired by [`reentrancy_guard_enter`] with the same `write_perm`.
+pub fn reentrancy_guard_exit(write_perm: bool) {
+    if write_perm {
+        storage_remove(&reentrancy_guard_key());
+    }
+}
+
+/// Guards a critical section of a contract method against reentrancy.
+///
+/// Unlike the guard of `#[contract]`, which covers whole methods, it protects a named section: a
+/// guard key is written on [`Self::enter`] and removed when the guard is dropped. Entering the same
+/// section again before that fails:
+///
+/// ```ignore
+/// fn withdraw(&mut self, amount: u128) -> Result<(), ReentrancyError> {
+///     let _guard = ReentrancyGuard::enter(b"withdraw")?;
+///     // Calls into other contracts can't enter this section again
+///     Ok(())
+/// }
+/// ```
+pub struct ReentrancyGuard {
+    key: Vec<u8>,
+}
+
+impl ReentrancyGuard {
+    /// Enters `section` of the current contract instance.
+    ///
+    /// Returns [`ReentrancyError::AlreadyEntered`] if another guard of the same section is alive.
+    pub fn enter(section: &[u8]) -> Result<Self, ReentrancyError> {
+        let mut key = reentrancy_guard_key();
+        key.push(b':');
+        key.extend_from_slice(section);
+        // The key is only replaced if the section is already entered, which leaves it unchanged
+        if storage_write(&key, b"") {
+            return Err(ReentrancyError::AlreadyEntered);
+        }
+        Ok(Self { key })
+    }
+}
+
+impl Drop for ReentrancyGuard {
+    fn drop(&mut self) {
+        storage_remove(&self.key);
+    }
+}
+
 /// Returns the address of the account that owns the current contract.
 pub fn contract_owner_address() -> Address {
     #[cfg(test)]
@@ -227,6 +602,11 @@ pub fn caller_address() -> Address {
 }
 
 /// Returns the address of the current contract's instance.
+///
+/// Instance addresses are assigned by the VM on deploy and the derivation scheme isn't part of the
+/// host interface, so the SDK can't compute the address of an instance in advance. A factory
+/// contract should let the new instance report its address, e.g. with a call back to the factory,
+/// and check it with [`contract_code_address_of`].
 pub fn contract_instance_address() -> Address {
     #[cfg(test)]
     {
@@ -238,6 +618,26 @@ pub fn contract_instance_address() -> Address {
         .unwrap_or_else(|_| abort())
 }
 
+/// Returns `true` if [`caller_address`] is [`contract_owner_address`].
+pub fn is_owner() -> bool {
+    caller_address().ct_eq(&contract_owner_address())
+}
+
+/// Panics unless [`caller_address`] is [`contract_owner_address`].
+pub fn assert_owner() {
+    if !is_owner() {
+        panic("Only the contract owner can call this method");
+    }
+}
+
+/// Panics unless [`caller_address`] is [`contract_instance_address`], i.e. unless the contract
+/// called itself. Use it for methods that are only meant to be called back by the contract.
+pub fn assert_self() {
+    if !caller_address().ct_eq(&contract_instance_address()) {
+        panic("Only the contract itself can call this method");
+    }
+}
+
 /// Returns the address of the account that owns the given contract instance
 ///
 /// # Panics
@@ -272,6 +672,10 @@ pub fn contract_code_owner_address_of(code_address: Address) -> Address {
 
 /// Returns the address of the contract code that is used for the given contract instance
 ///
+/// The VM doesn't expose the hash of the contract code. To verify that an instance was deployed
+/// from trusted code, e.g. in a factory contract, compare the returned address with the address of
+/// the trusted code.
+///
 /// # Panics
 ///
 /// If the contract instance is not found by the given address
@@ -288,136 +692,448 @@ pub fn contract_code_address_of(instance_address: Address) -> Address {
 ///
 /// If `Address` not found, returns `0`
 pub fn address_balance(address: &Address) -> Balance {
-    let address_vec = address.to_vec();
-    unsafe {
-        l1x_sys::address_balance(
-            address_vec.as_ptr() as _,
-            address_vec.len() as _,
-            ATOMIC_OP_REGISTER,
-        )
-    };
-    let bytes = expect_register(read_register(ATOMIC_OP_REGISTER));
-
-    u128::from_le_bytes(bytes.try_into().unwrap_or_else(|_| abort()))
+    #[cfg(test)]
+    {
+        return tests::address_balance(address);
+    }
+    #[cfg(not(test))]
+    {
+        let address_vec = address.to_vec();
+        unsafe {
+            l1x_sys::address_balance(
+                address_vec.as_ptr() as _,
+                address_vec.len() as _,
+                ATOMIC_OP_REGISTER,
+            )
+        };
+        let bytes = expect_register(read_register(ATOMIC_OP_REGISTER));
+
+        u128::from_le_bytes(bytes.try_into().unwrap_or_else(|_| abort()))
+    }
 }
 
 /// Transfers `amount` of L1X tokens from [`contract_instance_address`] to the specified address
 ///
 /// # Panics
 ///
-/// Panics if transfer failed
+/// Panics if transfer failed. Use [`try_transfer_to`] to handle the failure
 pub fn transfer_to(to: &Address, amount: Balance) {
-    let to_address_vec = to.to_vec();
-    let amount = amount.to_le_bytes();
-    match unsafe {
-        l1x_sys::transfer_to(
-            to_address_vec.as_ptr() as _,
-            to_address_vec.len() as _,
-            amount.as_ptr() as _,
-            amount.len() as _,
-        )
-    } {
-        1 => (),
-        0 => crate::panic("Transfer tokens from the contract balance failed"),
-        _ => abort(),
+    if try_transfer_to(to, amount).is_err() {
+        crate::panic("Transfer tokens from the contract balance failed");
+    }
+}
+
+/// Transfers `amount` of L1X tokens from [`contract_instance_address`] to the specified address
+///
+/// Returns [`TransferError::InsufficientFunds`] if the contract balance is less than `amount` and
+/// [`TransferError::TransferFailed`] if the transfer failed for another reason.
+pub fn try_transfer_to(to: &Address, amount: Balance) -> Result<(), TransferError> {
+    #[cfg(test)]
+    let transferred = tests::transfer(&contract_instance_address(), to, amount);
+    #[cfg(not(test))]
+    let transferred = {
+        let to_address_vec = to.to_vec();
+        let amount = amount.to_le_bytes();
+        match unsafe {
+            l1x_sys::transfer_to(
+                to_address_vec.as_ptr() as _,
+                to_address_vec.len() as _,
+                amount.as_ptr() as _,
+                amount.len() as _,
+            )
+        } {
+            1 => true,
+            0 => false,
+            _ => abort(),
+        }
     };
+    if transferred {
+        Ok(())
+    } else {
+        Err(transfer_error(&contract_instance_address(), amount))
+    }
+}
+
+/// Pulls `amount` of L1X tokens from [`caller_address`] to [`contract_instance_address`], e.g. as
+/// the payment of a payable method.
+///
+/// The VM has no notion of a deposit attached to a call, so a payable method has to transfer the
+/// funds from the caller itself.
+///
+/// # Panics
+///
+/// Panics with a message telling the caller why the transfer failed
+pub fn require_transfer_from_caller(amount: Balance) {
+    match try_transfer_from_caller(amount) {
+        Ok(()) => {}
+        Err(TransferError::InsufficientFunds) => panic(&format!(
+            "The caller balance is less than the required amount of {}",
+            amount
+        )),
+        Err(TransferError::TransferFailed) => {
+            panic("Transfer of the required amount from the caller failed")
+        }
+    }
+}
+
+/// Tells why transferring `amount` from `from` failed. The host only reports the failure, so the
+/// balance is checked after the fact.
+fn transfer_error(from: &Address, amount: Balance) -> TransferError {
+    if address_balance(from) < amount {
+        TransferError::InsufficientFunds
+    } else {
+        TransferError::TransferFailed
+    }
 }
 
 /// Transfers `amount` of L1X tokens from [`caller_address`] to [`contract_instance_address`]
 ///
 /// # Panics
 ///
-/// Panics if transfer failed
+/// Panics if transfer failed. Use [`try_transfer_from_caller`] to handle the failure
 pub fn transfer_from_caller(amount: Balance) {
-    let amount = amount.to_le_bytes();
-    match unsafe { l1x_sys::transfer_from_caller(amount.as_ptr() as _, amount.len() as _) } {
-        1 => (),
-        0 => crate::panic("Transfer tokens from the caller balance failed"),
-        _ => abort(),
+    if try_transfer_from_caller(amount).is_err() {
+        crate::panic("Transfer tokens from the caller balance failed");
+    }
+}
+
+/// Transfers `amount` of L1X tokens from [`caller_address`] to [`contract_instance_address`]
+///
+/// Returns [`TransferError::InsufficientFunds`] if the caller balance is less than `amount` and
+/// [`TransferError::TransferFailed`] if the transfer failed for another reason.
+pub fn try_transfer_from_caller(amount: Balance) -> Result<(), TransferError> {
+    #[cfg(test)]
+    let transferred = tests::transfer(&caller_address(), &contract_instance_address(), amount);
+    #[cfg(not(test))]
+    let transferred = {
+        let amount = amount.to_le_bytes();
+        match unsafe { l1x_sys::transfer_from_caller(amount.as_ptr() as _, amount.len() as _) } {
+            1 => true,
+            0 => false,
+            _ => abort(),
+        }
+    };
+    if transferred {
+        Ok(())
+    } else {
+        Err(transfer_error(&caller_address(), amount))
     }
 }
 
 /// Returns the hash of the current block
 pub fn block_hash() -> BlockHash {
-    let mut buf = BlockHash::default();
+    #[cfg(test)]
+    {
+        return tests::block_hash();
+    }
+    #[cfg(not(test))]
+    {
+        let mut buf = BlockHash::default();
 
-    unsafe { l1x_sys::block_hash(buf.as_mut_ptr() as _, buf.len() as _) };
+        unsafe { l1x_sys::block_hash(buf.as_mut_ptr() as _, buf.len() as _) };
 
-    buf
+        buf
+    }
 }
 
 /// Returns the number of the current block
 pub fn block_number() -> BlockNumber {
-    let mut buf = [0u8; std::mem::size_of::<BlockNumber>()];
+    #[cfg(test)]
+    {
+        return tests::block_number();
+    }
+    #[cfg(not(test))]
+    {
+        let mut buf = [0u8; std::mem::size_of::<BlockNumber>()];
 
-    unsafe { l1x_sys::block_number(buf.as_mut_ptr() as _, buf.len() as _) };
+        unsafe { l1x_sys::block_number(buf.as_mut_ptr() as _, buf.len() as _) };
 
-    BlockNumber::from_le_bytes(buf)
+        BlockNumber::from_le_bytes(buf)
+    }
 }
 
-/// Returns the timestamp of the current block
+/// The number of milliseconds in a second.
+pub const MS_PER_SECOND: TimeStamp = 1_000;
+
+/// The number of [`block_timestamp`] units in a millisecond, taken from the L1X VM spec: the VM
+/// reports block timestamps in milliseconds since the Unix epoch.
+pub const TIMESTAMP_UNITS_PER_MS: TimeStamp = 1;
+
+/// Returns the timestamp of the current block as reported by the VM
+///
+/// The value is in VM units, see [`TIMESTAMP_UNITS_PER_MS`]. Prefer [`block_timestamp_ms`] and
+/// [`block_timestamp_secs`] when the unit matters.
 pub fn block_timestamp() -> TimeStamp {
-    let mut buf = [0u8; std::mem::size_of::<TimeStamp>()];
+    #[cfg(test)]
+    {
+        return tests::block_timestamp();
+    }
+    #[cfg(not(test))]
+    {
+        let mut buf = [0u8; std::mem::size_of::<TimeStamp>()];
 
-    unsafe { l1x_sys::block_timestamp(buf.as_mut_ptr() as _, buf.len() as _) };
+        unsafe { l1x_sys::block_timestamp(buf.as_mut_ptr() as _, buf.len() as _) };
 
-    TimeStamp::from_le_bytes(buf)
+        TimeStamp::from_le_bytes(buf)
+    }
 }
 
-/// Returns the total amount of `Gas` that is allowed the contract to burn out
-pub fn gas_limit() -> Gas {
-    unsafe { l1x_sys::gas_limit() }
+/// Returns the timestamp of the current block in milliseconds since the Unix epoch
+pub fn block_timestamp_ms() -> TimeStamp {
+    block_timestamp() / TIMESTAMP_UNITS_PER_MS
 }
 
-/// Returns the amount of available `Gas`
-pub fn gas_left() -> Gas {
-    unsafe { l1x_sys::gas_left() }
+/// Returns the timestamp of the current block in whole seconds since the Unix epoch
+pub fn block_timestamp_secs() -> TimeStamp {
+    block_timestamp_ms() / MS_PER_SECOND
 }
 
-/// Returns `Balance` of the current contract's instance.
-pub fn contract_instance_balance() -> Balance {
-    address_balance(&contract_instance_address())
+/// Returns the total amount of `Gas` that is allowed the contract to burn out
+pub fn gas_limit() -> Gas {
+    #[cfg(test)]
+    {
+        return tests::gas_limit();
+    }
+    #[cfg(not(test))]
+    unsafe {
+        l1x_sys::gas_limit()
+    }
+}
+
+/// Returns the amount of available `Gas`
+pub fn gas_left() -> Gas {
+    #[cfg(test)]
+    {
+        return tests::gas_left();
+    }
+    #[cfg(not(test))]
+    unsafe {
+        l1x_sys::gas_left()
+    }
+}
+
+/// Calls `body` repeatedly while [`gas_left`] is above `reserve`.
+///
+/// Returns `true` if `body` finished by returning [`ControlFlow::Break`] and `false` if the loop
+/// stopped to keep `reserve` gas, so the contract can persist a cursor and return instead of
+/// running out of gas and reverting. `reserve` has to cover one iteration of `body` in addition to
+/// the cleanup, because the gas is only checked between iterations.
+pub fn with_gas_reserve(reserve: Gas, mut body: impl FnMut() -> ControlFlow<()>) -> bool {
+    while gas_left() > reserve {
+        if body().is_break() {
+            return true;
+        }
+    }
+    false
+}
+
+/// Returns the version of the runtime that executes the contract.
+///
+/// Host functions that were added in later runtime versions aren't available on older VMs, so a
+/// contract can gate such calls by comparing the result against the known version constants:
+///
+/// ```ignore
+/// const RUNTIME_WITH_FEATURE: u64 = 2;
+///
+/// if l1x_sdk::runtime_version() >= RUNTIME_WITH_FEATURE {
+///     // Use the new host function
+/// } else {
+///     // Fall back
+/// }
+/// ```
+pub fn runtime_version() -> u64 {
+    #[cfg(test)]
+    {
+        return tests::runtime_version();
+    }
+    #[cfg(not(test))]
+    unsafe {
+        l1x_sys::current_runtime_version()
+    }
+}
+
+/// Returns the `Balance` of each of the given addresses, in the same order
+///
+/// The VM has no batched balance query, so every address is a separate [`address_balance`] host
+/// call. Limit the number of addresses, e.g. with pagination, to keep the gas cost bounded.
+pub fn address_balances(addresses: &[Address]) -> Vec<Balance> {
+    addresses.iter().map(address_balance).collect()
+}
+
+/// Returns `Balance` of the current contract's instance.
+pub fn contract_instance_balance() -> Balance {
+    address_balance(&contract_instance_address())
 }
 
 /// Calls another contract
 ///
+/// Only already deployed instances can be called. The VM has no host function to deploy a contract
+/// from within a contract, so a factory contract can't instantiate its children itself. Deploy them
+/// with a transaction and register the instance addresses with the factory instead.
+///
 /// # Panics
 ///
 /// - If deserialization of `call` failed
 /// - If `call.read_only` is `false` but `call_contract` is called from read-only context
 /// - If there is not enough `Gas` to satisfy `gas_limit`
 pub fn call_contract(call: &ContractCall) -> Result<Vec<u8>, String> {
-    let call = call
+    #[cfg(test)]
+    {
+        return tests::call_contract(call);
+    }
+
+    #[cfg(not(test))]
+    {
+        let call = call
+            .try_to_vec()
+            .expect("Can't serialize the function arguments");
+        match unsafe {
+            sys::call_contract2(call.as_ptr() as _, call.len() as _, ATOMIC_OP_REGISTER)
+        } {
+            0 => Err(
+                String::from_utf8_lossy(&expect_register(read_register(ATOMIC_OP_REGISTER)))
+                    .to_string(),
+            ),
+            1 => Ok(expect_register(read_register(ATOMIC_OP_REGISTER))),
+            _ => abort(),
+        }
+    }
+}
+
+/// Calls another contract like [`call_contract`] and deserializes the response from JSON.
+///
+/// If the response can't be deserialized as `R`, returns `Err` with the description of the failure.
+///
+/// # Panics
+///
+/// See [`call_contract`]
+pub fn call_contract_typed<R>(call: &ContractCall) -> Result<R, String>
+where
+    R: DeserializeOwned,
+{
+    let response = call_contract(call)?;
+    serde_json::from_slice(&response).map_err(|e| {
+        format!(
+            "Failed to deserialize the response of `{}` from JSON: {}",
+            call.method_name, e
+        )
+    })
+}
+
+/// Calls another contract like [`call_contract`] and deserializes the response with Borsh.
+///
+/// If the response can't be deserialized as `R`, returns `Err` with the description of the failure.
+///
+/// # Panics
+///
+/// See [`call_contract`]
+pub fn call_contract_typed_borsh<R>(call: &ContractCall) -> Result<R, String>
+where
+    R: BorshDeserialize,
+{
+    let response = call_contract(call)?;
+    R::try_from_slice(&response).map_err(|e| {
+        format!(
+            "Failed to deserialize the response of `{}` with Borsh: {}",
+            call.method_name, e
+        )
+    })
+}
+
+/// An event with a stable name and version that [`emit`] wraps into an [`EventEnvelope`].
+///
+/// Usually implemented with `#[derive(Event)]`, which uses the type name as [`Event::NAME`].
+pub trait Event {
+    /// The name off-chain indexers identify the event by
+    const NAME: &'static str;
+    /// The version of the payload layout. Bump it when the payload changes
+    const VERSION: u8;
+}
+
+/// The Borsh layout of the events emitted by [`emit`].
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq)]
+pub struct EventEnvelope<E> {
+    /// [`Event::NAME`]
+    pub name: String,
+    /// [`Event::VERSION`]
+    pub version: u8,
+    /// The contract instance that emitted the event
+    pub contract: Address,
+    /// The emitted event
+    pub payload: E,
+}
+
+/// Emits `event` wrapped into an [`EventEnvelope`] with its name, version and the
+/// [`contract_instance_address`]. See [`emit_event`] for the error handling.
+pub fn emit<E>(event: E) -> Result<(), EventError>
+where
+    E: Event + BorshSerialize,
+{
+    emit_event(EventEnvelope {
+        name: E::NAME.to_string(),
+        version: E::VERSION,
+        contract: contract_instance_address(),
+        payload: event,
+    })
+}
+
+/// Emits the event. This `event` is stored on chain.
+///
+/// Unlike [`emit_event_experimental`], a failed emit doesn't abort the transaction, so the caller
+/// decides whether the event is critical.
+pub fn emit_event<T>(event: T) -> Result<(), EventError>
+where
+    T: BorshSerialize,
+{
+    let event_data = event
         .try_to_vec()
-        .expect("Can't serialize the function arguments");
-    match unsafe { sys::call_contract2(call.as_ptr() as _, call.len() as _, ATOMIC_OP_REGISTER) } {
-        0 => Err(
-            String::from_utf8_lossy(&expect_register(read_register(ATOMIC_OP_REGISTER)))
-                .to_string(),
-        ),
-        1 => Ok(expect_register(read_register(ATOMIC_OP_REGISTER))),
-        _ => abort(),
+        .map_err(|_| EventError::SerializationFailed)?;
+    if emit_event_data(event_data) {
+        Ok(())
+    } else {
+        Err(EventError::EmitFailed)
     }
 }
 
 /// Emits the event. This `event` is stored on chain.
+///
+/// # Panics
+///
+/// Panics if the event can't be serialized and aborts the transaction if the event can't be emitted
+#[deprecated(
+    note = "aborts the transaction if the event can't be emitted, use `emit_event` instead"
+)]
 pub fn emit_event_experimental<T>(event: T)
 where
     T: BorshSerialize,
 {
     let event_data = event.try_to_vec().expect("Can't serialize the event");
-    match unsafe { sys::emit_event_experimental(event_data.as_ptr() as _, event_data.len() as _) } {
-        0 => abort(),
-        _ => (),
+    if !emit_event_data(event_data) {
+        abort()
+    }
+}
+
+fn emit_event_data(event_data: Vec<u8>) -> bool {
+    #[cfg(test)]
+    {
+        return tests::emit_event(event_data);
+    }
+    #[cfg(not(test))]
+    unsafe {
+        sys::emit_event_experimental(event_data.as_ptr() as _, event_data.len() as _) != 0
     }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::types::Address;
+    use crate::contract_interaction::ContractCall;
+    use crate::types::{Address, Balance, BlockHash, BlockNumber, Gas, TimeStamp};
+    use borsh::{BorshDeserialize, BorshSerialize};
     use std::cell::RefCell;
     use std::collections::HashMap;
+    use std::ops::ControlFlow;
+    use std::rc::Rc;
 
     thread_local! {
         static MOCK_DATA: RefCell<MockData> = RefCell::new(MockData::new());
@@ -429,18 +1145,38 @@ mod tests {
 
     pub struct MockData {
         storage: HashMap<Vec<u8>, Vec<u8>>,
+        storage_reads: usize,
+        storage_writes: usize,
+        write_perm: bool,
         input: Option<Vec<u8>>,
         output: Vec<u8>,
         messages: Vec<String>,
         contract_owner_address: Address,
         caller_address: Address,
         contract_instance_address: Address,
+        contract_responses: HashMap<(Address, String), Result<Vec<u8>, String>>,
+        recorded_calls: Vec<ContractCall>,
+        block_hash: BlockHash,
+        block_number: BlockNumber,
+        block_timestamp: TimeStamp,
+        gas_limit: Gas,
+        gas_left: Gas,
+        gas_per_host_call: Gas,
+        runtime_version: u64,
+        balances: HashMap<Address, Balance>,
+        events: Vec<Vec<u8>>,
+        event_failure: bool,
+        transfer_failure: bool,
+        registers: HashMap<u64, Vec<u8>>,
     }
 
     impl MockData {
         pub fn new() -> Self {
             Self {
                 storage: HashMap::new(),
+                storage_reads: 0,
+                storage_writes: 0,
+                write_perm: true,
                 input: Some(Vec::new()),
                 output: Vec::new(),
                 messages: Vec::new(),
@@ -451,26 +1187,91 @@ mod tests {
                 contract_instance_address: Address::test_create_address(
                     &CONTRACT_INSTANCE_ADDRESS.to_vec(),
                 ),
+                contract_responses: HashMap::new(),
+                recorded_calls: Vec::new(),
+                block_hash: BlockHash::default(),
+                block_number: 0,
+                block_timestamp: 0,
+                gas_limit: Gas::MAX,
+                gas_left: Gas::MAX,
+                gas_per_host_call: 0,
+                runtime_version: 0,
+                balances: HashMap::new(),
+                events: Vec::new(),
+                event_failure: false,
+                transfer_failure: false,
+                registers: HashMap::new(),
             }
         }
+
+        /// Burns `gas_per_host_call` gas, see [`set_mock_gas_per_host_call`]
+        fn charge_host_call(&mut self) {
+            self.gas_left = self.gas_left.saturating_sub(self.gas_per_host_call);
+        }
+    }
+
+    pub fn register_len(register_id: u64) -> Option<u64> {
+        MOCK_DATA.with(|data| {
+            let mock_data = data.borrow();
+            let value = mock_data.registers.get(&register_id)?;
+            Some(value.len() as u64)
+        })
+    }
+
+    pub fn read_register(register_id: u64) -> Option<Vec<u8>> {
+        MOCK_DATA.with(|data| data.borrow().registers.get(&register_id).cloned())
+    }
+
+    pub fn write_register(register_id: u64, data: &[u8]) {
+        MOCK_DATA.with(|mock_data| {
+            mock_data
+                .borrow_mut()
+                .registers
+                .insert(register_id, data.to_vec())
+        });
     }
 
     pub fn storage_write(key: &[u8], value: &[u8]) -> bool {
         MOCK_DATA.with(|data| {
             let mut mock_data = data.borrow_mut();
-            // Check if the key is already in the storage
-            let is_new_insertion = !mock_data.storage.contains_key(key);
-            mock_data.storage.insert(key.to_vec(), value.to_vec());
-            is_new_insertion
+            mock_data.charge_host_call();
+            mock_data.storage_writes += 1;
+            // Like the host function, returns whether an existing value was replaced
+            mock_data
+                .storage
+                .insert(key.to_vec(), value.to_vec())
+                .is_some()
         })
     }
 
     pub fn storage_read(key: &[u8]) -> Option<Vec<u8>> {
-        MOCK_DATA.with(|data| data.borrow().storage.get(key).cloned())
+        MOCK_DATA.with(|data| {
+            let mut mock_data = data.borrow_mut();
+            mock_data.charge_host_call();
+            mock_data.storage_reads += 1;
+            mock_data.storage.get(key).cloned()
+        })
+    }
+
+    /// Unlike `storage_read`, doesn't copy the value and isn't counted by `get_mock_storage_reads`
+    pub fn storage_has_key(key: &[u8]) -> bool {
+        MOCK_DATA.with(|data| {
+            let mut mock_data = data.borrow_mut();
+            mock_data.charge_host_call();
+            mock_data.storage.contains_key(key)
+        })
     }
 
     pub fn storage_remove(key: &[u8]) -> bool {
-        MOCK_DATA.with(|data| data.borrow_mut().storage.remove(key).is_some())
+        MOCK_DATA.with(|data| {
+            let mut mock_data = data.borrow_mut();
+            mock_data.charge_host_call();
+            mock_data.storage.remove(key).is_some()
+        })
+    }
+
+    pub fn storage_write_perm() -> bool {
+        MOCK_DATA.with(|data| data.borrow().write_perm)
     }
 
     pub fn contract_owner_address() -> Address {
@@ -485,6 +1286,88 @@ mod tests {
         MOCK_DATA.with(|data| data.borrow().contract_instance_address.clone())
     }
 
+    pub fn call_contract(call: &ContractCall) -> Result<Vec<u8>, String> {
+        MOCK_DATA.with(|data| {
+            let mut mock_data = data.borrow_mut();
+            mock_data.charge_host_call();
+            mock_data.recorded_calls.push(call.clone());
+            mock_data
+                .contract_responses
+                .get(&(call.contract_address, call.method_name.clone()))
+                .cloned()
+                .unwrap_or_else(|| {
+                    Err(format!(
+                        "No mocked response for `{}` of {}",
+                        call.method_name, call.contract_address
+                    ))
+                })
+        })
+    }
+
+    pub fn address_balance(address: &Address) -> Balance {
+        MOCK_DATA.with(|data| {
+            data.borrow()
+                .balances
+                .get(address)
+                .copied()
+                .unwrap_or_default()
+        })
+    }
+
+    /// Moves `amount` from `from` to `to` in the mocked ledger. Returns `false` on insufficient funds
+    pub fn transfer(from: &Address, to: &Address, amount: Balance) -> bool {
+        MOCK_DATA.with(|data| {
+            let mut mock_data = data.borrow_mut();
+            mock_data.charge_host_call();
+            if mock_data.transfer_failure {
+                return false;
+            }
+            let from_balance = mock_data.balances.get(from).copied().unwrap_or_default();
+            let Some(from_balance) = from_balance.checked_sub(amount) else {
+                return false;
+            };
+            mock_data.balances.insert(*from, from_balance);
+            *mock_data.balances.entry(*to).or_default() += amount;
+            true
+        })
+    }
+
+    pub fn emit_event(event_data: Vec<u8>) -> bool {
+        MOCK_DATA.with(|data| {
+            let mut mock_data = data.borrow_mut();
+            mock_data.charge_host_call();
+            if mock_data.event_failure {
+                return false;
+            }
+            mock_data.events.push(event_data);
+            true
+        })
+    }
+
+    pub fn gas_limit() -> Gas {
+        MOCK_DATA.with(|data| data.borrow().gas_limit)
+    }
+
+    pub fn gas_left() -> Gas {
+        MOCK_DATA.with(|data| data.borrow().gas_left)
+    }
+
+    pub fn runtime_version() -> u64 {
+        MOCK_DATA.with(|data| data.borrow().runtime_version)
+    }
+
+    pub fn block_hash() -> BlockHash {
+        MOCK_DATA.with(|data| data.borrow().block_hash)
+    }
+
+    pub fn block_number() -> BlockNumber {
+        MOCK_DATA.with(|data| data.borrow().block_number)
+    }
+
+    pub fn block_timestamp() -> TimeStamp {
+        MOCK_DATA.with(|data| data.borrow().block_timestamp)
+    }
+
     pub fn remove_from_mock_storage(key: &[u8]) -> bool {
         MOCK_DATA.with(|data| data.borrow_mut().storage.remove(key).is_some())
     }
@@ -493,6 +1376,10 @@ mod tests {
         MOCK_DATA.with(|data| data.borrow().input.clone())
     }
 
+    pub fn input_len() -> Option<usize> {
+        MOCK_DATA.with(|data| data.borrow().input.as_ref().map(Vec::len))
+    }
+
     pub fn output(data: &[u8]) {
         MOCK_DATA.with(|data_refcell| {
             let mut data_inside = data_refcell.borrow_mut();
@@ -519,6 +1406,55 @@ mod tests {
         MOCK_DATA.with(|data| data.borrow().messages.clone())
     }
 
+    /// Returns the number of `storage_read` calls made so far
+    pub fn get_mock_storage_reads() -> usize {
+        MOCK_DATA.with(|data| data.borrow().storage_reads)
+    }
+
+    /// Returns the number of `storage_write` calls made so far
+    pub fn get_mock_storage_writes() -> usize {
+        MOCK_DATA.with(|data| data.borrow().storage_writes)
+    }
+
+    /// Returns the Borsh serialized events emitted so far, in order
+    pub fn get_mock_events() -> Vec<Vec<u8>> {
+        MOCK_DATA.with(|data| data.borrow().events.clone())
+    }
+
+    /// Returns the events emitted so far, deserialized as `T`
+    ///
+    /// Panics if an event can't be deserialized as `T`
+    pub fn get_mock_events_as<T>() -> Vec<T>
+    where
+        T: BorshDeserialize,
+    {
+        get_mock_events()
+            .iter()
+            .map(|event| T::try_from_slice(event).expect("Can't deserialize the event"))
+            .collect()
+    }
+
+    /// Resets all mock state to the defaults: storage, I/O, addresses, block info, gas, balances,
+    /// events and recorded calls.
+    ///
+    /// The mock state is thread-local, so tests that may share a thread, e.g. when run with
+    /// `--test-threads=1`, should call this first.
+    pub fn reset_mock() {
+        MOCK_DATA.with(|data| *data.borrow_mut() = MockData::new());
+        super::STORAGE_READ_CACHE.with(|cache| cache.borrow_mut().clear());
+    }
+
+    /// Removes all the values from the mock storage and resets the read/write counters
+    pub fn clear_mock_storage() {
+        MOCK_DATA.with(|data| {
+            let mut data = data.borrow_mut();
+            data.storage.clear();
+            data.storage_reads = 0;
+            data.storage_writes = 0;
+        });
+        super::STORAGE_READ_CACHE.with(|cache| cache.borrow_mut().clear());
+    }
+
     pub fn clear_mock_io() {
         MOCK_DATA.with(|data| {
             let mut data = data.borrow_mut();
@@ -528,6 +1464,71 @@ mod tests {
         })
     }
 
+    /// Makes `call_contract` return `response` for calls of `method_name` in the contract at `address`
+    pub fn set_mock_contract_response(
+        address: Address,
+        method_name: &str,
+        response: Result<Vec<u8>, String>,
+    ) {
+        MOCK_DATA.with(|data| {
+            data.borrow_mut()
+                .contract_responses
+                .insert((address, method_name.to_string()), response);
+        })
+    }
+
+    /// Returns all calls made with `call_contract` so far, in order
+    pub fn get_recorded_calls() -> Vec<ContractCall> {
+        MOCK_DATA.with(|data| data.borrow().recorded_calls.clone())
+    }
+
+    pub fn set_mock_block_hash(block_hash: BlockHash) {
+        MOCK_DATA.with(|data| data.borrow_mut().block_hash = block_hash)
+    }
+
+    pub fn set_mock_block_number(block_number: BlockNumber) {
+        MOCK_DATA.with(|data| data.borrow_mut().block_number = block_number)
+    }
+
+    pub fn set_mock_block_timestamp(block_timestamp: TimeStamp) {
+        MOCK_DATA.with(|data| data.borrow_mut().block_timestamp = block_timestamp)
+    }
+
+    pub fn set_mock_balance(address: Address, balance: Balance) {
+        MOCK_DATA.with(|data| data.borrow_mut().balances.insert(address, balance));
+    }
+
+    pub fn set_mock_gas_limit(gas_limit: Gas) {
+        MOCK_DATA.with(|data| data.borrow_mut().gas_limit = gas_limit)
+    }
+
+    pub fn set_mock_gas_left(gas_left: Gas) {
+        MOCK_DATA.with(|data| data.borrow_mut().gas_left = gas_left)
+    }
+
+    /// Makes each mocked storage access and contract call burn `gas` from `gas_left`. Disabled (`0`) by default
+    pub fn set_mock_gas_per_host_call(gas: Gas) {
+        MOCK_DATA.with(|data| data.borrow_mut().gas_per_host_call = gas)
+    }
+
+    pub fn set_mock_runtime_version(runtime_version: u64) {
+        MOCK_DATA.with(|data| data.borrow_mut().runtime_version = runtime_version)
+    }
+
+    /// Makes the mocked host reject emitted events, like a failed `emit_event_experimental` host call
+    pub fn set_mock_event_failure(event_failure: bool) {
+        MOCK_DATA.with(|data| data.borrow_mut().event_failure = event_failure)
+    }
+
+    /// Makes the mocked host reject transfers even if the balance is sufficient
+    pub fn set_mock_transfer_failure(transfer_failure: bool) {
+        MOCK_DATA.with(|data| data.borrow_mut().transfer_failure = transfer_failure)
+    }
+
+    pub fn set_mock_write_perm(write_perm: bool) {
+        MOCK_DATA.with(|data| data.borrow_mut().write_perm = write_perm)
+    }
+
     pub fn set_mock_contract_owner_address(owner_address: Vec<u8>) {
         MOCK_DATA.with(|data| {
             data.borrow_mut().contract_owner_address = Address::test_create_address(&owner_address)
@@ -555,6 +1556,8 @@ mod tests {
         let value = b"value";
 
         // Write to storage
+        assert!(!storage_write(key, value));
+        // Overwrite
         assert!(storage_write(key, value));
 
         // Read from storage
@@ -568,6 +1571,147 @@ mod tests {
         assert!(storage_read(key).is_none());
     }
 
+    #[test]
+    fn test_storage_write_many() {
+        let entries: Vec<(Vec<u8>, Vec<u8>)> = (0u32..100)
+            .map(|i| (i.to_le_bytes().to_vec(), (i * 2).to_le_bytes().to_vec()))
+            .collect();
+
+        crate::storage_write_many(&entries);
+
+        for (key, value) in &entries {
+            assert_eq!(storage_read(key).as_ref(), Some(value));
+        }
+        crate::storage_write_many(&[]);
+    }
+
+    #[test]
+    fn test_storage_has_key() {
+        storage_write(b"key", b"value");
+        let reads = get_mock_storage_reads();
+
+        assert!(crate::storage_has_key(b"key"));
+        assert!(!crate::storage_has_key(b"missing"));
+        assert_eq!(get_mock_storage_reads(), reads);
+    }
+
+    #[test]
+    fn test_storage_read_cached() {
+        storage_write(b"key", b"value");
+        let reads = get_mock_storage_reads();
+
+        let first = crate::storage_read_cached(b"key").unwrap();
+        let second = crate::storage_read_cached(b"key").unwrap();
+        assert_eq!(*first, b"value");
+        assert!(Rc::ptr_eq(&first, &second));
+        assert_eq!(get_mock_storage_reads(), reads + 1);
+
+        // Missing keys are cached as well
+        assert_eq!(crate::storage_read_cached(b"missing"), None);
+        assert_eq!(crate::storage_read_cached(b"missing"), None);
+        assert_eq!(get_mock_storage_reads(), reads + 2);
+    }
+
+    #[test]
+    fn test_storage_read_cached_invalidation() {
+        storage_write(b"key", b"value");
+        assert_eq!(*crate::storage_read_cached(b"key").unwrap(), b"value");
+
+        crate::storage_write(b"key", b"new value");
+        assert_eq!(*crate::storage_read_cached(b"key").unwrap(), b"new value");
+
+        crate::storage_remove(b"key");
+        assert_eq!(crate::storage_read_cached(b"key"), None);
+
+        crate::storage_write(b"key", b"value");
+        clear_mock_storage();
+        assert_eq!(crate::storage_read_cached(b"key"), None);
+    }
+
+    #[test]
+    fn test_storage_reads_counter() {
+        let before = get_mock_storage_reads();
+
+        storage_read(b"key");
+        storage_read(b"key");
+
+        assert_eq!(get_mock_storage_reads(), before + 2);
+    }
+
+    #[test]
+    fn test_storage_writes_counter() {
+        let before = get_mock_storage_writes();
+
+        storage_write(b"key", b"value");
+        storage_read(b"key");
+
+        assert_eq!(get_mock_storage_writes(), before + 1);
+    }
+
+    #[test]
+    fn test_reentrancy_guard_lifecycle() {
+        let key = crate::reentrancy_guard_key();
+        assert!(key.starts_with(crate::REENTRANCY_GUARD_KEY_PREFIX));
+        assert!(key.ends_with(contract_instance_address().as_bytes()));
+
+        crate::reentrancy_guard_enter(true);
+        assert!(storage_read(&key).is_some());
+
+        crate::reentrancy_guard_exit(true);
+        assert!(storage_read(&key).is_none());
+
+        // Entering again after the guard is released is fine
+        crate::reentrancy_guard_enter(true);
+        crate::reentrancy_guard_exit(true);
+    }
+
+    #[test]
+    fn test_reentrancy_guard_blocks_reentrant_call() {
+        // A calls B, B calls back into A (any method of A)
+        crate::reentrancy_guard_enter(true);
+
+        let result = std::panic::catch_unwind(|| crate::reentrancy_guard_enter(true));
+        assert!(result.is_err());
+        assert!(get_mock_msgs().contains(&"Found a cross-contract call loop".to_string()));
+
+        // A read-only call back into A is rejected as well
+        let result = std::panic::catch_unwind(|| crate::reentrancy_guard_enter(false));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_reentrancy_guard_read_only() {
+        let writes = get_mock_storage_writes();
+
+        crate::reentrancy_guard_enter(false);
+        crate::reentrancy_guard_exit(false);
+
+        assert_eq!(get_mock_storage_writes(), writes);
+    }
+
+    #[test]
+    fn test_reentrancy_guard_section() {
+        let guard = crate::ReentrancyGuard::enter(b"withdraw").unwrap();
+        let mut key = crate::reentrancy_guard_key();
+        key.extend_from_slice(b":withdraw");
+        assert!(storage_read(&key).is_some());
+
+        assert_eq!(
+            crate::ReentrancyGuard::enter(b"withdraw").err(),
+            Some(crate::ReentrancyError::AlreadyEntered)
+        );
+        // Other sections and the method guard are independent
+        let other = crate::ReentrancyGuard::enter(b"deposit").unwrap();
+        crate::reentrancy_guard_enter(true);
+        crate::reentrancy_guard_exit(true);
+
+        drop(guard);
+        assert!(storage_read(&key).is_none());
+        drop(crate::ReentrancyGuard::enter(b"withdraw").unwrap());
+        drop(other);
+        assert!(crate::ReentrancyGuard::enter(b"deposit").is_ok());
+    }
+
     #[test]
     fn test_msg() {
         let message = "Test message";
@@ -578,6 +1722,37 @@ mod tests {
         assert_eq!(mock_messages[0], message);
     }
 
+    #[test]
+    fn test_log_json() {
+        #[derive(serde::Serialize)]
+        struct Deposit {
+            kind: &'static str,
+            amount: crate::types::U128,
+        }
+
+        crate::log_json(&Deposit {
+            kind: "deposit",
+            amount: 10.into(),
+        });
+
+        assert_eq!(
+            get_mock_msgs(),
+            vec![r#"EVENT_JSON:{"kind":"deposit","amount":"10"}"#]
+        );
+    }
+
+    #[test]
+    fn test_input_len() {
+        assert_eq!(crate::input_len(), Some(0));
+
+        set_mock_input(vec![1, 2, 3, 4]);
+        assert_eq!(crate::input_len(), Some(4));
+        assert_eq!(crate::input_len(), input().map(|input| input.len()));
+
+        clear_mock_io();
+        assert_eq!(crate::input_len(), None);
+    }
+
     #[test]
     fn test_input_output() {
         let data = vec![1, 2, 3, 4];
@@ -659,6 +1834,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_assert_owner() {
+        assert!(!crate::is_owner());
+
+        set_mock_caller_address(CONTRACT_OWNER_ADDRESS.to_vec());
+        assert!(crate::is_owner());
+        crate::assert_owner();
+    }
+
+    #[test]
+    #[should_panic(expected = "Mocked panic function called!")]
+    fn test_assert_owner_denied() {
+        crate::assert_owner();
+    }
+
+    #[test]
+    fn test_assert_self() {
+        set_mock_caller_address(CONTRACT_INSTANCE_ADDRESS.to_vec());
+        crate::assert_self();
+        assert!(!crate::is_owner());
+    }
+
+    #[test]
+    #[should_panic(expected = "Mocked panic function called!")]
+    fn test_assert_self_denied() {
+        set_mock_caller_address(CONTRACT_OWNER_ADDRESS.to_vec());
+        crate::assert_self();
+    }
+
     #[test]
     fn test_input_and_output() {
         let data = vec![1, 2, 3];
@@ -674,6 +1878,108 @@ mod tests {
         assert_eq!(get_mock_output(), data);
     }
 
+    #[derive(
+        BorshSerialize, BorshDeserialize, serde::Serialize, serde::Deserialize, Debug, PartialEq,
+    )]
+    struct Transfer {
+        to: String,
+        amount: u64,
+    }
+
+    #[test]
+    fn test_input_and_output_json() {
+        let transfer = Transfer {
+            to: "alice".to_string(),
+            amount: 10,
+        };
+
+        set_mock_input(br#"{"to":"alice","amount":10}"#.to_vec());
+        assert_eq!(crate::input_json::<Transfer>(), Some(transfer));
+
+        let transfer: Transfer = crate::input_json().unwrap();
+        crate::output_json(&transfer);
+        assert_eq!(get_mock_output(), br#"{"to":"alice","amount":10}"#);
+
+        clear_mock_io();
+        assert_eq!(crate::input_json::<Transfer>(), None);
+    }
+
+    #[test]
+    fn test_input_and_output_borsh() {
+        let transfer = Transfer {
+            to: "bob".to_string(),
+            amount: 20,
+        };
+
+        set_mock_input(transfer.try_to_vec().unwrap());
+        assert_eq!(crate::input_borsh::<Transfer>().as_ref(), Some(&transfer));
+
+        crate::output_borsh(&transfer);
+        assert_eq!(
+            Transfer::try_from_slice(&get_mock_output()).unwrap(),
+            transfer
+        );
+
+        clear_mock_io();
+        assert_eq!(crate::input_borsh::<Transfer>(), None);
+    }
+
+    #[test]
+    fn test_decode_args() {
+        let to = Address::test_create_address(&b"mock_alice_address11".to_vec());
+
+        set_mock_input((10u64, to).try_to_vec().unwrap());
+        let (amount, recipient): (u64, Address) = crate::decode_args();
+        assert_eq!(amount, 10);
+        assert_eq!(recipient, to);
+
+        set_mock_input((1u8, "memo".to_string(), true).try_to_vec().unwrap());
+        assert_eq!(
+            crate::decode_args::<(u8, String, bool)>(),
+            (1, "memo".to_string(), true)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Mocked panic function called!")]
+    fn test_decode_args_trailing_bytes() {
+        set_mock_input((1u64, 2u64, 3u8).try_to_vec().unwrap());
+        crate::decode_args::<(u64, u64)>();
+    }
+
+    #[test]
+    fn test_clear_mock_storage() {
+        storage_write(b"key", b"value");
+        set_mock_input(vec![1]);
+
+        clear_mock_storage();
+
+        assert!(storage_read(b"key").is_none());
+        assert_eq!(get_mock_storage_writes(), 0);
+        assert_eq!(input(), Some(vec![1]));
+    }
+
+    #[test]
+    fn test_reset_mock() {
+        storage_write(b"key", b"value");
+        set_mock_input(vec![1]);
+        set_mock_caller_address(CONTRACT_OWNER_ADDRESS.to_vec());
+        set_mock_block_number(10);
+        set_mock_gas_left(5);
+        set_mock_balance(caller_address(), 100);
+        crate::emit_event(1u8).unwrap();
+
+        reset_mock();
+
+        assert!(storage_read(b"key").is_none());
+        assert_eq!(input(), Some(Vec::new()));
+        assert!(!crate::is_owner());
+        assert_eq!(crate::block_number(), 0);
+        assert_eq!(crate::gas_left(), Gas::MAX);
+        assert_eq!(crate::address_balance(&caller_address()), 0);
+        assert!(get_mock_events().is_empty());
+    }
+
     #[test]
     fn test_clear_mock_io() {
         // Set some mock input/output data and a message
@@ -689,4 +1995,408 @@ mod tests {
         assert_eq!(get_mock_output(), vec![] as Vec<u8>);
         assert_eq!(get_mock_msgs(), Vec::<String>::new());
     }
+
+    #[derive(
+        serde::Serialize, serde::Deserialize, BorshSerialize, BorshDeserialize, Debug, PartialEq,
+    )]
+    struct TokenBalance {
+        owner: String,
+        amount: u64,
+    }
+
+    fn balance_call() -> ContractCall {
+        ContractCall::builder(contract_instance_address(), "balance_of")
+            .read_only(true)
+            .build()
+    }
+
+    #[test]
+    fn test_call_contract_typed() {
+        let balance = TokenBalance {
+            owner: "alice".to_string(),
+            amount: 42,
+        };
+        set_mock_contract_response(
+            contract_instance_address(),
+            "balance_of",
+            Ok(serde_json::to_vec(&balance).unwrap()),
+        );
+
+        assert_eq!(
+            crate::call_contract_typed::<TokenBalance>(&balance_call()),
+            Ok(balance)
+        );
+    }
+
+    #[test]
+    fn test_call_contract_typed_borsh() {
+        let balance = TokenBalance {
+            owner: "alice".to_string(),
+            amount: 42,
+        };
+        set_mock_contract_response(
+            contract_instance_address(),
+            "balance_of",
+            Ok(balance.try_to_vec().unwrap()),
+        );
+
+        assert_eq!(
+            crate::call_contract_typed_borsh::<TokenBalance>(&balance_call()),
+            Ok(balance)
+        );
+    }
+
+    #[test]
+    fn test_call_contract_typed_errors() {
+        set_mock_contract_response(
+            contract_instance_address(),
+            "balance_of",
+            Err("Out of gas".to_string()),
+        );
+        assert_eq!(
+            crate::call_contract_typed::<TokenBalance>(&balance_call()),
+            Err("Out of gas".to_string())
+        );
+
+        set_mock_contract_response(
+            contract_instance_address(),
+            "balance_of",
+            Ok(b"not json".to_vec()),
+        );
+        let err = crate::call_contract_typed::<TokenBalance>(&balance_call()).unwrap_err();
+        assert!(err.contains("balance_of"));
+
+        set_mock_contract_response(contract_instance_address(), "balance_of", Ok(vec![1]));
+        assert!(crate::call_contract_typed_borsh::<TokenBalance>(&balance_call()).is_err());
+    }
+
+    #[test]
+    fn test_mock_contract_responses_and_recorded_calls() {
+        let token = Address::test_create_address(&b"mock_token_address11".to_vec());
+        let balance = TokenBalance {
+            owner: "alice".to_string(),
+            amount: 7,
+        };
+        set_mock_contract_response(
+            token,
+            "balance_of",
+            Ok(serde_json::to_vec(&balance).unwrap()),
+        );
+
+        let call = ContractCall::builder(token, "balance_of")
+            .args_json(&serde_json::json!({ "owner": "alice" }))
+            .read_only(true)
+            .build();
+        assert_eq!(
+            crate::call_contract_typed::<TokenBalance>(&call),
+            Ok(balance)
+        );
+
+        // Not stubbed
+        let unknown = ContractCall::builder(token, "total_supply").build();
+        assert!(crate::call_contract(&unknown).is_err());
+
+        assert_eq!(get_recorded_calls(), vec![call, unknown]);
+    }
+
+    #[test]
+    fn test_block_info() {
+        // Deterministic defaults
+        assert_eq!(crate::block_hash(), [0u8; 32]);
+        assert_eq!(crate::block_number(), 0);
+        assert_eq!(crate::block_timestamp(), 0);
+
+        set_mock_block_hash([7u8; 32]);
+        set_mock_block_number(1_000);
+        set_mock_block_timestamp(1_700_000_000);
+
+        assert_eq!(crate::block_hash(), [7u8; 32]);
+        assert_eq!(crate::block_number(), 1_000);
+        assert_eq!(crate::block_timestamp(), 1_700_000_000);
+    }
+
+    #[test]
+    fn test_block_timestamp_units() {
+        // 2023-11-14T22:13:20.999Z
+        set_mock_block_timestamp(1_700_000_000_999 * crate::TIMESTAMP_UNITS_PER_MS);
+        assert_eq!(crate::block_timestamp_ms(), 1_700_000_000_999);
+        // Rounded down to whole seconds
+        assert_eq!(crate::block_timestamp_secs(), 1_700_000_000);
+    }
+
+    #[test]
+    fn test_gas() {
+        set_mock_gas_limit(1_000);
+        set_mock_gas_left(400);
+
+        assert_eq!(crate::gas_limit(), 1_000);
+        assert_eq!(crate::gas_left(), 400);
+
+        // Gas isn't burnt unless enabled
+        storage_write(b"key", b"value");
+        assert_eq!(crate::gas_left(), 400);
+    }
+
+    #[test]
+    fn test_gas_exhaustion() {
+        set_mock_gas_left(1_000);
+        set_mock_gas_per_host_call(300);
+
+        // Writes until the gas is low, like a contract processing a queue would
+        let mut writes = 0;
+        while crate::gas_left() >= 300 {
+            crate::storage_write(&[writes], b"value");
+            writes += 1;
+        }
+
+        assert_eq!(writes, 3);
+        assert_eq!(crate::gas_left(), 100);
+
+        // Never underflows
+        crate::storage_write(b"key", b"value");
+        assert_eq!(crate::gas_left(), 0);
+    }
+
+    #[test]
+    fn test_with_gas_reserve() {
+        set_mock_gas_left(1_000);
+        set_mock_gas_per_host_call(100);
+
+        let mut cursor = 0u32;
+        let completed = crate::with_gas_reserve(250, || {
+            crate::storage_write(&cursor.to_le_bytes(), b"value");
+            cursor += 1;
+            ControlFlow::Continue(())
+        });
+
+        assert!(!completed);
+        assert_eq!(cursor, 8);
+        assert_eq!(crate::gas_left(), 200);
+
+        // Enough gas left for the cleanup
+        crate::storage_write(b"cursor", &cursor.to_le_bytes());
+        assert_eq!(crate::gas_left(), 100);
+    }
+
+    #[test]
+    fn test_with_gas_reserve_completes() {
+        set_mock_gas_left(1_000);
+        set_mock_gas_per_host_call(100);
+
+        let mut items = vec![1, 2, 3];
+        let completed = crate::with_gas_reserve(250, || match items.pop() {
+            Some(item) => {
+                crate::storage_write(&[item], b"value");
+                ControlFlow::Continue(())
+            }
+            None => ControlFlow::Break(()),
+        });
+
+        assert!(completed);
+        assert!(items.is_empty());
+        assert_eq!(crate::gas_left(), 700);
+    }
+
+    #[test]
+    fn test_runtime_version() {
+        assert_eq!(crate::runtime_version(), 0);
+
+        set_mock_runtime_version(3);
+        assert_eq!(crate::runtime_version(), 3);
+    }
+
+    #[test]
+    fn test_address_balance() {
+        let alice = Address::test_create_address(&b"mock_alice_address11".to_vec());
+        assert_eq!(crate::address_balance(&alice), 0);
+
+        set_mock_balance(alice, 100);
+        assert_eq!(crate::address_balance(&alice), 100);
+    }
+
+    #[test]
+    fn test_address_balances() {
+        let addresses = [
+            Address::test_create_address(&b"mock_alice_address11".to_vec()),
+            Address::test_create_address(&b"mock_bob_address1111".to_vec()),
+            Address::test_create_address(&b"mock_carol_address11".to_vec()),
+        ];
+        for (address, balance) in addresses.iter().zip([100, 0, 5]) {
+            set_mock_balance(*address, balance);
+        }
+
+        assert_eq!(crate::address_balances(&addresses), vec![100, 0, 5]);
+        assert_eq!(
+            crate::address_balances(&[addresses[2], Address::zero(), addresses[0]]),
+            vec![5, 0, 100]
+        );
+        assert_eq!(crate::address_balances(&[]), Vec::<Balance>::new());
+    }
+
+    #[test]
+    fn test_transfer_to() {
+        let alice = Address::test_create_address(&b"mock_alice_address11".to_vec());
+        set_mock_balance(contract_instance_address(), 100);
+
+        crate::transfer_to(&alice, 30);
+
+        assert_eq!(crate::address_balance(&alice), 30);
+        assert_eq!(crate::contract_instance_balance(), 70);
+    }
+
+    #[test]
+    fn test_transfer_from_caller() {
+        set_mock_balance(caller_address(), 100);
+
+        crate::transfer_from_caller(40);
+
+        assert_eq!(crate::address_balance(&caller_address()), 60);
+        assert_eq!(crate::contract_instance_balance(), 40);
+    }
+
+    #[test]
+    #[should_panic(expected = "Mocked panic function called!")]
+    fn test_transfer_insufficient_funds() {
+        set_mock_balance(contract_instance_address(), 10);
+
+        let alice = Address::test_create_address(&b"mock_alice_address11".to_vec());
+        crate::transfer_to(&alice, 11);
+    }
+
+    #[test]
+    fn test_try_transfer_to() {
+        let alice = Address::test_create_address(&b"mock_alice_address11".to_vec());
+        set_mock_balance(contract_instance_address(), 10);
+
+        assert_eq!(
+            crate::try_transfer_to(&alice, 11),
+            Err(crate::TransferError::InsufficientFunds)
+        );
+        assert_eq!(crate::contract_instance_balance(), 10);
+
+        set_mock_transfer_failure(true);
+        assert_eq!(
+            crate::try_transfer_to(&alice, 5),
+            Err(crate::TransferError::TransferFailed)
+        );
+        assert_eq!(crate::contract_instance_balance(), 10);
+
+        set_mock_transfer_failure(false);
+        assert_eq!(crate::try_transfer_to(&alice, 5), Ok(()));
+        assert_eq!(crate::address_balance(&alice), 5);
+        assert_eq!(crate::contract_instance_balance(), 5);
+    }
+
+    #[test]
+    fn test_try_transfer_from_caller() {
+        set_mock_balance(caller_address(), 10);
+
+        assert_eq!(
+            crate::try_transfer_from_caller(11),
+            Err(crate::TransferError::InsufficientFunds)
+        );
+        assert_eq!(crate::address_balance(&caller_address()), 10);
+
+        set_mock_transfer_failure(true);
+        assert_eq!(
+            crate::try_transfer_from_caller(5),
+            Err(crate::TransferError::TransferFailed)
+        );
+
+        set_mock_transfer_failure(false);
+        assert_eq!(crate::try_transfer_from_caller(10), Ok(()));
+        assert_eq!(crate::address_balance(&caller_address()), 0);
+        assert_eq!(crate::contract_instance_balance(), 10);
+    }
+
+    #[test]
+    fn test_require_transfer_from_caller() {
+        set_mock_balance(caller_address(), 100);
+
+        crate::require_transfer_from_caller(100);
+
+        assert_eq!(crate::address_balance(&caller_address()), 0);
+        assert_eq!(crate::contract_instance_balance(), 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "Mocked panic function called!")]
+    fn test_require_transfer_from_caller_insufficient() {
+        set_mock_balance(caller_address(), 99);
+
+        crate::require_transfer_from_caller(100);
+    }
+
+    #[test]
+    fn test_events() {
+        #[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+        struct Transfer {
+            to: String,
+            amount: u64,
+        }
+
+        let first = Transfer {
+            to: "alice".to_string(),
+            amount: 1,
+        };
+        let second = Transfer {
+            to: "bob".to_string(),
+            amount: 2,
+        };
+        crate::emit_event(&first).unwrap();
+        #[allow(deprecated)]
+        crate::emit_event_experimental(&second);
+
+        assert_eq!(get_mock_events()[0], first.try_to_vec().unwrap());
+        assert_eq!(get_mock_events_as::<Transfer>(), vec![first, second]);
+    }
+
+    #[test]
+    fn test_emit() {
+        #[derive(BorshSerialize, BorshDeserialize, crate::Event, Debug, PartialEq)]
+        struct Minted {
+            amount: u64,
+        }
+
+        #[derive(BorshSerialize, BorshDeserialize, crate::Event, Debug, PartialEq)]
+        #[event(version = 3)]
+        struct Burned(u64);
+
+        crate::emit(Minted { amount: 5 }).unwrap();
+        crate::emit(Burned(2)).unwrap();
+
+        let events = get_mock_events();
+        assert_eq!(
+            crate::EventEnvelope::<Minted>::try_from_slice(&events[0]).unwrap(),
+            crate::EventEnvelope {
+                name: "Minted".to_string(),
+                version: 1,
+                contract: contract_instance_address(),
+                payload: Minted { amount: 5 },
+            }
+        );
+        let burned = crate::EventEnvelope::<Burned>::try_from_slice(&events[1]).unwrap();
+        assert_eq!(burned.name, "Burned");
+        assert_eq!(burned.version, 3);
+        assert_eq!(burned.payload, Burned(2));
+    }
+
+    #[test]
+    fn test_emit_event_failure() {
+        set_mock_event_failure(true);
+        assert_eq!(crate::emit_event(1u64), Err(crate::EventError::EmitFailed));
+        assert!(get_mock_events().is_empty());
+
+        set_mock_event_failure(false);
+        assert_eq!(crate::emit_event(2u64), Ok(()));
+        assert_eq!(get_mock_events_as::<u64>(), vec![2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_emit_event_experimental_failure() {
+        set_mock_event_failure(true);
+        #[allow(deprecated)]
+        crate::emit_event_experimental(1u64);
+    }
 }
@@ -0,0 +1,104 @@
+//! Leveled log messages.
+//!
+//! [`crate::msg`] takes an arbitrary string, so off-chain tools can't tell a debug note from an
+//! error. These functions prefix the message with a level tag, e.g. `[WARN] Low liquidity`, and
+//! emit it with [`crate::msg`]:
+//!
+//! ```ignore
+//! use l1x_sdk::log;
+//!
+//! log::info("Deposit received");
+//! log::warn(&format!("Low liquidity: {}", reserve));
+//! log::log_with_address(log::Level::Error, "Oracle price is stale");
+//! ```
+//!
+//! The message is formatted by the caller, so nothing is formatted for levels that aren't used.
+
+/// The severity of a log message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    /// Returns the tag the messages of this level are prefixed with, e.g. `[INFO]`.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Level::Info => "[INFO]",
+            Level::Warn => "[WARN]",
+            Level::Error => "[ERROR]",
+        }
+    }
+}
+
+/// Emits `message` prefixed with the tag of `level`, e.g. `[INFO] message`.
+pub fn log(level: Level, message: &str) {
+    crate::msg(&format!("{} {}", level.tag(), message));
+}
+
+/// Emits `message` prefixed with the tag of `level` and the address of the contract instance, e.g.
+/// `[INFO] a11ce00000000000000000000000000000000000: message`.
+///
+/// Useful when the logs of several contracts called within a transaction are mixed together.
+pub fn log_with_address(level: Level, message: &str) {
+    crate::msg(&format!(
+        "{} {}: {}",
+        level.tag(),
+        crate::contract_instance_address(),
+        message
+    ));
+}
+
+/// Emits `message` with the [`Level::Info`] tag.
+pub fn info(message: &str) {
+    log(Level::Info, message);
+}
+
+/// Emits `message` with the [`Level::Warn`] tag.
+pub fn warn(message: &str) {
+    log(Level::Warn, message);
+}
+
+/// Emits `message` with the [`Level::Error`] tag.
+pub fn error(message: &str) {
+    log(Level::Error, message);
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_levels() {
+        info("Deposit received");
+        warn(&format!("Low liquidity: {}", 5));
+        error("Oracle price is stale");
+
+        assert_eq!(
+            get_mock_msgs(),
+            vec![
+                "[INFO] Deposit received",
+                "[WARN] Low liquidity: 5",
+                "[ERROR] Oracle price is stale",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_log_with_address() {
+        log_with_address(Level::Warn, "Low liquidity");
+
+        assert_eq!(
+            get_mock_msgs().pop().unwrap(),
+            format!(
+                "[WARN] {}: Low liquidity",
+                crate::contract_instance_address()
+            )
+        );
+    }
+}
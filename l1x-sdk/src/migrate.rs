@@ -0,0 +1,140 @@
+//! Helpers for upgrading the contract state to a new layout.
+//!
+//! Borsh isn't self-describing, so a state written with an old struct can't be read with a new
+//! one. Keep the old struct around and convert the state once, e.g. in a dedicated `migrate`
+//! method of the upgraded contract:
+//!
+//! ```ignore
+//! #[derive(BorshSerialize, BorshDeserialize)]
+//! struct StateV1 {
+//!     owner: Address,
+//!     total: u64,
+//! }
+//!
+//! #[derive(BorshSerialize, BorshDeserialize)]
+//! struct StateV2 {
+//!     owner: Address,
+//!     total: u64,
+//!     paused: bool,
+//! }
+//!
+//! migrate_state::<StateV1, StateV2, _>(l1x_sdk::STATE_KEY, |old| StateV2 {
+//!     owner: old.owner,
+//!     total: old.total,
+//!     paused: false,
+//! });
+//! ```
+//!
+//! The code itself can't be replaced from within the contract: the VM has no host function to
+//! update the code of a contract instance, so the SDK can't offer a self-upgrade call. Deploy the
+//! new code with a transaction and call the migration method afterwards.
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// Reads the raw bytes stored under `key`.
+///
+/// If the storage doesn't have the key present, returns `None`
+pub fn read_raw_state(key: &[u8]) -> Option<Vec<u8>> {
+    crate::storage_read(key)
+}
+
+/// Writes `state` serialized with Borsh under `key`.
+///
+/// # Panic
+///
+/// Panics if serialization fails
+pub fn write_state<T>(key: &[u8], state: &T)
+where
+    T: BorshSerialize,
+{
+    crate::state::save(key, state)
+}
+
+/// Reads the state stored under `key` as `Old`, converts it with `f` and writes it back as `New`.
+///
+/// Returns `false` without calling `f` if the storage doesn't have the key present.
+///
+/// # Panic
+///
+/// Panics if the stored state can't be deserialized as `Old`. Running the migration twice
+/// therefore fails instead of silently corrupting the state, unless the old layout happens to be a
+/// prefix of the new one.
+pub fn migrate_state<Old, New, F>(key: &[u8], f: F) -> bool
+where
+    Old: BorshDeserialize,
+    New: BorshSerialize,
+    F: FnOnce(Old) -> New,
+{
+    let Some(bytes) = read_raw_state(key) else {
+        return false;
+    };
+    let old = Old::try_from_slice(&bytes)
+        .unwrap_or_else(|_| crate::panic("Cannot deserialize the old contract state"));
+    write_state(key, &f(old));
+    true
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(BorshSerialize, BorshDeserialize)]
+    struct StateV1 {
+        owner: String,
+        total: u64,
+    }
+
+    #[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+    struct StateV2 {
+        owner: String,
+        total: u64,
+        paused: bool,
+    }
+
+    #[test]
+    fn test_migrate_state() {
+        write_state(
+            b"state",
+            &StateV1 {
+                owner: "alice".to_string(),
+                total: 10,
+            },
+        );
+
+        let migrated = migrate_state(b"state", |old: StateV1| StateV2 {
+            owner: old.owner,
+            total: old.total * 2,
+            paused: true,
+        });
+
+        assert!(migrated);
+        assert_eq!(
+            StateV2::try_from_slice(&read_raw_state(b"state").unwrap()).unwrap(),
+            StateV2 {
+                owner: "alice".to_string(),
+                total: 20,
+                paused: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_migrate_missing_state() {
+        let migrated = migrate_state(b"missing", |_: StateV1| -> StateV2 { unreachable!() });
+
+        assert!(!migrated);
+        assert_eq!(read_raw_state(b"missing"), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_migrate_invalid_state() {
+        write_state(b"state", &1u8);
+        migrate_state(b"state", |old: StateV1| StateV2 {
+            owner: old.owner,
+            total: old.total,
+            paused: false,
+        });
+    }
+}
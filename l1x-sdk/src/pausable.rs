@@ -0,0 +1,137 @@
+//! An emergency stop switch for contract methods.
+use borsh::{BorshDeserialize, BorshSerialize};
+
+const ERR_PAUSED: &str = "The contract is paused";
+
+/// A pause flag stored under its own storage key.
+///
+/// Keep it in the contract state and guard the methods that must stop in an emergency with
+/// [`Self::assert_not_paused`]. Only the contract owner can pause and unpause:
+///
+/// ```ignore
+/// #[derive(BorshSerialize, BorshDeserialize)]
+/// struct Token {
+///     pausable: Pausable,
+///     balances: LookupMap<Address, u128>,
+/// }
+///
+/// impl Token {
+///     pub fn transfer(&mut self, to: Address, amount: u128) {
+///         self.pausable.assert_not_paused();
+///         // ...
+///     }
+///
+///     pub fn pause(&mut self) {
+///         self.pausable.pause();
+///     }
+/// }
+/// ```
+///
+/// The flag is read from the storage on every check, so it doesn't depend on the contract state
+/// being saved after [`Self::pause`].
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct Pausable {
+    key: Box<[u8]>,
+}
+
+impl Pausable {
+    /// Creates a switch that keeps the flag under `key`. The contract isn't paused until
+    /// [`Self::pause`] is called.
+    pub fn new(key: Vec<u8>) -> Self {
+        Self {
+            key: key.into_boxed_slice(),
+        }
+    }
+
+    /// Returns `true` if the contract is paused.
+    pub fn is_paused(&self) -> bool {
+        crate::storage_has_key(&self.key)
+    }
+
+    /// Pauses the contract. Does nothing if it's already paused.
+    ///
+    /// # Panic
+    ///
+    /// Panics unless the caller is the contract owner, see [`crate::assert_owner`]
+    pub fn pause(&mut self) {
+        crate::assert_owner();
+        crate::storage_write(&self.key, b"");
+    }
+
+    /// Unpauses the contract. Does nothing if it isn't paused.
+    ///
+    /// # Panic
+    ///
+    /// Panics unless the caller is the contract owner, see [`crate::assert_owner`]
+    pub fn unpause(&mut self) {
+        crate::assert_owner();
+        crate::storage_remove(&self.key);
+    }
+
+    /// Panics with "The contract is paused" if the contract is paused.
+    pub fn assert_not_paused(&self) {
+        if self.is_paused() {
+            crate::panic(ERR_PAUSED);
+        }
+    }
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::*;
+    use super::*;
+
+    fn call_as_owner() {
+        set_mock_caller_address(crate::contract_owner_address().to_vec());
+    }
+
+    #[test]
+    fn test_pause_and_unpause() {
+        call_as_owner();
+        let mut pausable = Pausable::new(b"paused".to_vec());
+        assert!(!pausable.is_paused());
+        pausable.assert_not_paused();
+
+        pausable.pause();
+        assert!(pausable.is_paused());
+        pausable.pause();
+        assert!(pausable.is_paused());
+
+        // The flag is kept in the storage, not in the struct
+        let state = pausable.try_to_vec().unwrap();
+        let mut pausable = Pausable::try_from_slice(&state).unwrap();
+        assert!(pausable.is_paused());
+
+        pausable.unpause();
+        assert!(!pausable.is_paused());
+        pausable.assert_not_paused();
+        assert!(storage_read(b"paused").is_none());
+    }
+
+    #[test]
+    fn test_assert_not_paused() {
+        call_as_owner();
+        let mut pausable = Pausable::new(b"paused".to_vec());
+        pausable.pause();
+
+        assert!(std::panic::catch_unwind(|| pausable.assert_not_paused()).is_err());
+        assert_eq!(get_mock_msgs().pop().unwrap(), ERR_PAUSED);
+    }
+
+    #[test]
+    fn test_only_owner_can_pause() {
+        let mut pausable = Pausable::new(b"paused".to_vec());
+        let result = std::panic::catch_unwind(move || pausable.pause());
+        assert!(result.is_err());
+        assert!(storage_read(b"paused").is_none());
+
+        call_as_owner();
+        Pausable::new(b"paused".to_vec()).pause();
+        set_mock_caller_address(vec![7; 20]);
+        let mut pausable = Pausable::new(b"paused".to_vec());
+        assert!(std::panic::catch_unwind(move || pausable.unpause()).is_err());
+        assert!(Pausable::new(b"paused".to_vec()).is_paused());
+    }
+}
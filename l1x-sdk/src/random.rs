@@ -0,0 +1,133 @@
+//! Deterministic pseudo-random values derived from the block data.
+//!
+//! Requires the `crypto` feature.
+//!
+//! # Security
+//!
+//! This is **not** secure randomness. Every input of [`seed`] is known before the transaction is
+//! executed or can be chosen: block producers can influence the block hash and timestamp, and
+//! callers pick their address and when to submit the transaction. Another contract can compute the
+//! same seed and only call this one when the outcome suits it. Only use these values where nobody
+//! gains from predicting or steering them, e.g. cosmetic choices. Lotteries and games with stakes
+//! need a commit-reveal scheme or an oracle.
+//!
+//! ```ignore
+//! use l1x_sdk::random;
+//!
+//! let seed = random::seed();
+//! let dice = random::range(seed, 6) + 1;
+//! ```
+use crate::crypto::keccak256;
+
+/// Returns `keccak256(block_hash ++ block_timestamp ++ caller_address)`, with the timestamp
+/// encoded as 16 little-endian bytes.
+///
+/// The seed is the same for all the calls of the same caller within a block. Hash it together
+/// with a counter to get several independent values within a call.
+pub fn seed() -> [u8; 32] {
+    let mut preimage = Vec::with_capacity(32 + 16 + 20);
+    preimage.extend_from_slice(&crate::block_hash());
+    preimage.extend_from_slice(&crate::block_timestamp().to_le_bytes());
+    preimage.extend_from_slice(crate::caller_address().as_bytes());
+    keccak256(&preimage)
+}
+
+/// Returns a value in `0..n` derived from `seed`.
+///
+/// Reducing a random `u64` modulo `n` favors the small values unless `n` is a power of two, so the
+/// 8-byte little-endian words of the seed are tried in order and the ones from the incomplete last
+/// block of `n` values are rejected. If all the words are rejected, the seed is rehashed. This is
+/// astronomically unlikely for any `n`.
+///
+/// # Panic
+///
+/// Panics if `n` is zero
+pub fn range(seed: [u8; 32], n: u64) -> u64 {
+    if n == 0 {
+        crate::panic("The range is empty");
+    }
+    // The largest multiple of `n` that fits into `u64`, every value below it is equally likely
+    let limit = u64::MAX - u64::MAX % n;
+    let mut state = seed;
+    loop {
+        for i in 0..4 {
+            let word = state[i * 8..(i + 1) * 8].try_into();
+            let value = u64::from_le_bytes(word.unwrap_or_else(|_| crate::abort()));
+            if value < limit {
+                return value % n;
+            }
+        }
+        state = keccak256(&state);
+    }
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_seed_mixes_block_data() {
+        let initial = seed();
+        assert_eq!(seed(), initial);
+
+        set_mock_block_hash([1; 32]);
+        let with_hash = seed();
+        assert_ne!(with_hash, initial);
+
+        set_mock_block_timestamp(1);
+        let with_timestamp = seed();
+        assert_ne!(with_timestamp, with_hash);
+
+        set_mock_caller_address(vec![7; 20]);
+        assert_ne!(seed(), with_timestamp);
+
+        let mut preimage = [1; 32].to_vec();
+        preimage.extend_from_slice(&1u128.to_le_bytes());
+        preimage.extend_from_slice(&[7; 20]);
+        assert_eq!(seed(), keccak256(&preimage));
+    }
+
+    #[test]
+    fn test_range_distribution() {
+        let mut buckets = [0u32; 6];
+        for i in 0..6000u32 {
+            buckets[range(keccak256(&i.to_le_bytes()), 6) as usize] += 1;
+        }
+        for count in buckets {
+            assert!((850..1150).contains(&count), "{:?}", buckets);
+        }
+
+        assert!((0..100u32).all(|i| range(keccak256(&i.to_le_bytes()), 1) == 0));
+    }
+
+    #[test]
+    fn test_range_rejects_biased_words() {
+        // `u64::MAX` is a multiple of 3, so it's the single value of the incomplete last block
+        let mut seed = [0; 32];
+        seed[..8].copy_from_slice(&u64::MAX.to_le_bytes());
+        seed[8..16].copy_from_slice(&5u64.to_le_bytes());
+        assert_eq!(range(seed, 3), 2);
+
+        // For `n = 2^63 + 1` the words from `2^63 + 1` up are rejected
+        let n: u64 = (1 << 63) + 1;
+        seed[..8].copy_from_slice(&(n + 10).to_le_bytes());
+        seed[8..16].copy_from_slice(&(n - 1).to_le_bytes());
+        assert_eq!(range(seed, n), n - 1);
+
+        // The seed is rehashed when every word is rejected
+        let seed = [0xff; 32];
+        let rehashed = keccak256(&seed);
+        let first = u64::from_le_bytes(rehashed[..8].try_into().unwrap());
+        assert!(first < u64::MAX);
+        assert_eq!(range(seed, 3), first % 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty_range() {
+        range([0; 32], 0);
+    }
+}
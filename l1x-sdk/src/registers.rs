@@ -0,0 +1,99 @@
+//! Direct access to the VM registers.
+//!
+//! Host functions return variable sized data by writing it to a register chosen by the caller, the
+//! contract then reads the register into its memory. The SDK wraps every host function of
+//! [`l1x_sys`], so these functions are only needed to call host functions the SDK doesn't wrap
+//! yet:
+//!
+//! ```ignore
+//! use l1x_sdk::registers;
+//!
+//! const MY_REGISTER: u64 = 0;
+//!
+//! unsafe { my_host_function(MY_REGISTER) };
+//! let result = registers::read(MY_REGISTER).expect("The host function didn't write the result");
+//! ```
+//!
+//! # Reserved registers
+//!
+//! The SDK uses [`EVICTED_REGISTER`] and [`ATOMIC_OP_REGISTER`] internally and overwrites them on
+//! most calls. Don't use them to keep data between SDK calls.
+
+/// Receives the values replaced or removed by the storage functions.
+pub const EVICTED_REGISTER: u64 = std::u64::MAX - 1;
+/// Receives the results of the host functions called by the SDK.
+pub const ATOMIC_OP_REGISTER: u64 = std::u64::MAX - 2;
+
+/// Returns the size of the register. If register is not used returns `None`.
+pub fn len(register_id: u64) -> Option<u64> {
+    #[cfg(test)]
+    {
+        return crate::tests::register_len(register_id);
+    }
+    #[cfg(not(test))]
+    {
+        let len = unsafe { l1x_sys::register_len(register_id) };
+        if len == std::u64::MAX {
+            None
+        } else {
+            Some(len)
+        }
+    }
+}
+
+/// Reads the content of the `register_id`. If register is not used returns `None`.
+pub fn read(register_id: u64) -> Option<Vec<u8>> {
+    #[cfg(test)]
+    {
+        return crate::tests::read_register(register_id);
+    }
+    #[cfg(not(test))]
+    {
+        let len: usize = len(register_id)?
+            .try_into()
+            .unwrap_or_else(|_| crate::abort());
+
+        let mut buffer = Vec::with_capacity(len);
+
+        unsafe {
+            l1x_sys::read_register(register_id, buffer.as_mut_ptr() as u64);
+
+            buffer.set_len(len);
+        }
+        Some(buffer)
+    }
+}
+
+/// Writes `data` to the `register_id`, replacing its previous content.
+pub fn write(register_id: u64, data: &[u8]) {
+    #[cfg(test)]
+    {
+        return crate::tests::write_register(register_id, data);
+    }
+    #[cfg(not(test))]
+    unsafe {
+        l1x_sys::write_register(register_id, data.as_ptr() as _, data.len() as _)
+    }
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_and_read() {
+        assert_eq!(len(0), None);
+        assert_eq!(read(0), None);
+
+        write(0, b"value");
+        assert_eq!(len(0), Some(5));
+        assert_eq!(read(0), Some(b"value".to_vec()));
+
+        write(0, b"");
+        assert_eq!(len(0), Some(0));
+        assert_eq!(read(0), Some(Vec::new()));
+        assert_eq!(read(1), None);
+    }
+}
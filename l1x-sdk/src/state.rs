@@ -0,0 +1,123 @@
+//! Loading and saving Borsh serialized state under arbitrary storage keys.
+//!
+//! This is the state lifecycle of [`crate::contract`] for contracts that don't use the macro:
+//!
+//! ```ignore
+//! #[no_mangle]
+//! pub extern "C" fn increment() {
+//!     l1x_sdk::state::mutate_state(b"counter", |counter: &mut u64| *counter += 1);
+//! }
+//! ```
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// Reads the state stored under `key`.
+///
+/// If the storage doesn't have the key present, returns `None`.
+///
+/// # Panic
+///
+/// Panics if the stored state can't be deserialized as `T`
+pub fn load<T>(key: &[u8]) -> Option<T>
+where
+    T: BorshDeserialize,
+{
+    crate::storage_read(key).map(|bytes| {
+        T::try_from_slice(&bytes)
+            .unwrap_or_else(|_| crate::panic("Cannot deserialize the contract state"))
+    })
+}
+
+/// Reads the state stored under `key`, falling back to [`Default`] if the storage doesn't have the
+/// key present.
+///
+/// # Panic
+///
+/// Panics if the stored state can't be deserialized as `T`
+pub fn load_or_default<T>(key: &[u8]) -> T
+where
+    T: BorshDeserialize + Default,
+{
+    load(key).unwrap_or_default()
+}
+
+/// Writes `state` serialized with Borsh under `key`.
+///
+/// # Panic
+///
+/// Panics if serialization fails
+pub fn save<T>(key: &[u8], state: &T)
+where
+    T: BorshSerialize,
+{
+    let bytes = state
+        .try_to_vec()
+        .unwrap_or_else(|_| crate::panic("Cannot serialize the contract state"));
+    crate::storage_write(key, &bytes);
+}
+
+/// Loads the state stored under `key` with [`load_or_default`], calls `f` on it and saves it back.
+///
+/// Returns the result of `f`.
+pub fn mutate_state<T, R>(key: &[u8], f: impl FnOnce(&mut T) -> R) -> R
+where
+    T: BorshSerialize + BorshDeserialize + Default,
+{
+    let mut state = load_or_default(key);
+    let result = f(&mut state);
+    save(key, &state);
+    result
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(BorshSerialize, BorshDeserialize, Default, Debug, PartialEq)]
+    struct Config {
+        owner: String,
+        fee: u16,
+    }
+
+    #[test]
+    fn test_load_or_default() {
+        assert_eq!(load::<Config>(b"config"), None);
+        assert_eq!(load_or_default::<Config>(b"config"), Config::default());
+
+        // Loading doesn't write the default
+        assert!(crate::storage_read(b"config").is_none());
+    }
+
+    #[test]
+    fn test_save() {
+        let config = Config {
+            owner: "alice".to_string(),
+            fee: 30,
+        };
+        save(b"config", &config);
+
+        assert_eq!(load(b"config"), Some(config));
+    }
+
+    #[test]
+    fn test_mutate_state() {
+        let fee = mutate_state(b"config", |config: &mut Config| {
+            config.fee += 5;
+            config.fee
+        });
+        assert_eq!(fee, 5);
+
+        mutate_state(b"config", |config: &mut Config| {
+            config.owner = "bob".to_string();
+            config.fee *= 2;
+        });
+        assert_eq!(
+            load::<Config>(b"config"),
+            Some(Config {
+                owner: "bob".to_string(),
+                fee: 10,
+            })
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use super::Deque;
+
+impl<T> Extend<T> for Deque<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iter {
+            self.push_back(item)
+        }
+    }
+}
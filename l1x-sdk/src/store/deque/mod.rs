@@ -0,0 +1,237 @@
+//! A double-ended queue that stores its content to the persistent storage.
+mod impls;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use super::IndexMap;
+
+const ERR_CAPACITY_OVERFLOW: &str = "Deque capacity overflow";
+
+/// A double-ended queue that stores its content to the persistent storage.
+/// Uses the following map: index -> element.
+///
+/// The queue keeps a `head` cursor and a length. Elements occupy the indices
+/// `head..head + len` (wrapping around [`u32::MAX`]), so pushing and popping at both ends only
+/// touches a single storage key and never shifts other elements.
+///
+/// All operations are cached. The cache is flushed in the following cases:
+///
+/// * [`Self::flush`] method is called
+/// * [`drop`] method is called
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct Deque<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    head: u32,
+    len: u32,
+    values: IndexMap<T>,
+}
+
+impl<T> Deque<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    /// Creates a new empty queue. Uses `prefix` as a unique prefix for indices.
+    pub fn new(prefix: Vec<u8>) -> Self {
+        Self {
+            head: 0,
+            len: 0,
+            values: IndexMap::new(prefix),
+        }
+    }
+
+    /// Returns the number of elements in the queue.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns `true` if the queue contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn storage_index(&self, index: u32) -> u32 {
+        self.head.wrapping_add(index)
+    }
+
+    fn grow(&mut self) {
+        self.len = self
+            .len
+            .checked_add(1)
+            .unwrap_or_else(|| crate::panic(ERR_CAPACITY_OVERFLOW));
+    }
+
+    /// Appends an element to the back of the queue.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the new length exceeds [`u32::MAX`].
+    pub fn push_back(&mut self, element: T) {
+        let index = self.storage_index(self.len);
+        self.grow();
+        self.values.set(index, Some(element));
+    }
+
+    /// Prepends an element to the front of the queue.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the new length exceeds [`u32::MAX`].
+    pub fn push_front(&mut self, element: T) {
+        self.grow();
+        self.head = self.head.wrapping_sub(1);
+        self.values.set(self.head, Some(element));
+    }
+
+    /// Removes the first element and returns it, or `None` if the queue is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let value = self.values.remove(self.head);
+        self.head = self.head.wrapping_add(1);
+        self.len -= 1;
+        value
+    }
+
+    /// Removes the last element and returns it, or `None` if the queue is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        let last = self.len.checked_sub(1)?;
+        let value = self.values.remove(self.storage_index(last));
+        self.len = last;
+        value
+    }
+
+    /// Returns a reference to the element at `index` counting from the front of the queue, or
+    /// `None` if out of bounds.
+    pub fn get(&self, index: u32) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        self.values.get(self.storage_index(index))
+    }
+
+    /// Returns a reference to the first element, or `None` if the queue is empty.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a reference to the last element, or `None` if the queue is empty.
+    pub fn back(&self) -> Option<&T> {
+        self.len.checked_sub(1).and_then(|last| self.get(last))
+    }
+
+    /// Writes the cached operations to the persistent storage.
+    ///
+    /// # Panic
+    ///
+    /// Panics if serialization fails
+    pub fn flush(&mut self) {
+        self.values.flush();
+    }
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::tests::*;
+    use super::*;
+    use std::collections::VecDeque;
+
+    fn assert_matches_oracle(deque: &Deque<String>, oracle: &VecDeque<String>) {
+        assert_eq!(deque.len() as usize, oracle.len());
+        assert_eq!(deque.front(), oracle.front());
+        assert_eq!(deque.back(), oracle.back());
+        for (i, expected) in oracle.iter().enumerate() {
+            assert_eq!(deque.get(i as u32), Some(expected));
+        }
+        assert_eq!(deque.get(oracle.len() as u32), None);
+    }
+
+    #[test]
+    fn test_new() {
+        let deque: Deque<String> = Deque::new(b"test".to_vec());
+        assert!(deque.is_empty());
+        assert_eq!(deque.front(), None);
+        assert_eq!(deque.back(), None);
+    }
+
+    #[test]
+    fn test_fifo() {
+        let mut deque: Deque<String> = Deque::new(b"test".to_vec());
+        deque.push_back("a".to_string());
+        deque.push_back("b".to_string());
+
+        assert_eq!(deque.pop_front(), Some("a".to_string()));
+        assert_eq!(deque.pop_front(), Some("b".to_string()));
+        assert_eq!(deque.pop_front(), None);
+        assert_eq!(deque.pop_back(), None);
+    }
+
+    #[test]
+    fn test_interleaved_against_oracle() {
+        let mut deque: Deque<String> = Deque::new(b"test".to_vec());
+        let mut oracle = VecDeque::new();
+
+        // A simple deterministic sequence of operations
+        let mut seed: u32 = 7;
+        for i in 0..200 {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            let value = i.to_string();
+            match (seed >> 16) % 4 {
+                0 => {
+                    deque.push_back(value.clone());
+                    oracle.push_back(value);
+                }
+                1 => {
+                    deque.push_front(value.clone());
+                    oracle.push_front(value);
+                }
+                2 => assert_eq!(deque.pop_front(), oracle.pop_front()),
+                _ => assert_eq!(deque.pop_back(), oracle.pop_back()),
+            }
+            assert_matches_oracle(&deque, &oracle);
+        }
+    }
+
+    #[test]
+    fn test_pop_removes_only_cursor_key() {
+        let mut deque: Deque<String> = Deque::new(b"test".to_vec());
+        deque.push_front("a".to_string());
+        deque.push_back("b".to_string());
+        deque.push_back("c".to_string());
+        deque.flush();
+
+        let key = |index: u32| {
+            let mut key = b"test".to_vec();
+            key.extend_from_slice(&index.to_le_bytes());
+            key
+        };
+
+        // "a" wraps around to the last index
+        assert!(storage_read(&key(u32::MAX)).is_some());
+
+        deque.pop_front();
+        deque.pop_back();
+        deque.flush();
+
+        assert!(storage_read(&key(u32::MAX)).is_none());
+        assert!(storage_read(&key(0)).is_some());
+        assert!(storage_read(&key(1)).is_none());
+    }
+
+    #[test]
+    fn test_persistence() {
+        let mut deque: Deque<String> = Deque::new(b"test".to_vec());
+        deque.push_back("b".to_string());
+        deque.push_front("a".to_string());
+        deque.flush();
+
+        let restored = Deque::<String>::try_from_slice(&deque.try_to_vec().unwrap()).unwrap();
+        assert_eq!(restored.front(), Some(&"a".to_string()));
+        assert_eq!(restored.back(), Some(&"b".to_string()));
+    }
+}
@@ -1,14 +1,27 @@
+//! An implementation of a map from indices to values that stores its content directly on the persistent storage.
 use borsh::{BorshDeserialize, BorshSerialize};
 use once_cell::unsync::OnceCell;
 
+use super::FlushStats;
 use crate::utils::StableMap;
 use crate::{CacheEntry, EntryState};
 
 const ERR_ELEMENT_DESERIALIZATION: &str = "Cannot deserialize element";
 const ERR_ELEMENT_SERIALIZATION: &str = "Cannot serialize element";
 
+/// An implementation of a map from `u32` indices to values that stores its content directly on
+/// the persistent storage. This is the primitive [`crate::store::Vector`] is built on.
+///
+/// Each value is stored under the key `prefix ++ index.to_le_bytes()`. Values are loaded lazily
+/// and cached on the first access, so reading an index that has never been written returns `None`
+/// without writing anything to the storage.
+///
+/// All operations are cached. The cache is flushed in the following cases:
+///
+/// * [`Self::flush`] method is called
+/// * [`drop`] method is called
 #[derive(BorshSerialize, BorshDeserialize)]
-pub(crate) struct IndexMap<T>
+pub struct IndexMap<T>
 where
     T: BorshSerialize,
 {
@@ -22,6 +35,7 @@ impl<T> IndexMap<T>
 where
     T: BorshSerialize,
 {
+    /// Creates a new map. Uses `prefix` as a unique prefix for indices.
     pub fn new(prefix: Vec<u8>) -> Self {
         Self {
             prefix: prefix.into_boxed_slice(),
@@ -34,10 +48,28 @@ where
         buf.extend_from_slice(&index.to_le_bytes());
     }
 
+    /// Writes the cached operations to the persistent storage.
+    ///
+    /// Only modified entries are written. Entries set to `None` are removed from the storage.
+    ///
+    /// # Panic
+    ///
+    /// Panics if serialization fails
     pub fn flush(&mut self) {
+        self.flush_with_stats();
+    }
+
+    /// Writes the cached operations to the persistent storage like [`Self::flush`] and returns the
+    /// number of issued writes and removes.
+    ///
+    /// # Panic
+    ///
+    /// Panics if serialization fails
+    pub fn flush_with_stats(&mut self) -> FlushStats {
+        let mut stats = FlushStats::default();
         let mut buf = Vec::new();
         let mut key_buf = Vec::with_capacity(self.prefix.len() + 4);
-        for (k, v) in self.cache.inner().iter_mut() {
+        for (k, v) in self.cache.iter_mut() {
             if let Some(v) = v.get_mut() {
                 if v.is_modified() {
                     key_buf.clear();
@@ -48,9 +80,11 @@ where
                             BorshSerialize::serialize(modified, &mut buf)
                                 .unwrap_or_else(|_| crate::panic(ERR_ELEMENT_SERIALIZATION));
                             crate::storage_write(&key_buf, &buf);
+                            stats.writes += 1;
                         }
                         None => {
                             crate::storage_remove(&key_buf);
+                            stats.removes += 1;
                         }
                     }
 
@@ -58,8 +92,13 @@ where
                 }
             }
         }
+        stats
     }
 
+    /// Inserts or removes a value by the index.
+    ///
+    /// * If `value` is `None` then the value is removed from the storage on flush.
+    /// * If `value` is `Some(v)` then `v` is written to the storage on flush.
     pub fn set(&mut self, index: u32, value: Option<T>) {
         let entry = self.cache.get_mut(index);
         match entry.get_mut() {
@@ -79,6 +118,9 @@ where
         T::try_from_slice(raw_element).unwrap_or_else(|_| crate::panic(ERR_ELEMENT_DESERIALIZATION))
     }
 
+    /// Returns a reference to the value by the index.
+    ///
+    /// If the map doesn't have the index present, returns `None`
     pub fn get(&self, index: u32) -> Option<&T> {
         let entry = self.cache.get(index).get_or_init(|| {
             let mut buf = Vec::with_capacity(self.prefix.len() + 4);
@@ -104,8 +146,150 @@ where
         entry
     }
 
+    /// Returns a mutable reference to the value by the index.
+    ///
+    /// If the map doesn't have the index present, returns `None`
     pub fn get_mut(&mut self, index: u32) -> Option<&mut T> {
         let entry = self.get_mut_inner(index);
         entry.value_mut().as_mut()
     }
+
+    /// Removes the value by the index, returning the value if it was previously in the map.
+    pub fn remove(&mut self, index: u32) -> Option<T> {
+        self.get_mut_inner(index).replace(None)
+    }
+
+    /// Returns true if the map contains a value by the index.
+    pub fn contains(&self, index: u32) -> bool {
+        self.get(index).is_some()
+    }
+}
+
+impl<T> Drop for IndexMap<T>
+where
+    T: BorshSerialize,
+{
+    fn drop(&mut self) {
+        self.flush()
+    }
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::tests::*;
+    use super::*;
+
+    fn lookup_key(prefix: &[u8], index: u32) -> Vec<u8> {
+        let mut key = prefix.to_vec();
+        key.extend_from_slice(&index.to_le_bytes());
+        key
+    }
+
+    #[test]
+    fn test_set_and_get() {
+        let mut map: IndexMap<String> = IndexMap::new(b"test".to_vec());
+
+        map.set(0, Some("zero".to_string()));
+        map.set(5, Some("five".to_string()));
+
+        assert_eq!(map.get(0), Some(&"zero".to_string()));
+        assert_eq!(map.get(5), Some(&"five".to_string()));
+        assert_eq!(map.get(1), None);
+        assert!(map.contains(5));
+        assert!(!map.contains(1));
+    }
+
+    #[test]
+    fn test_get_mut() {
+        let mut map: IndexMap<String> = IndexMap::new(b"test".to_vec());
+
+        map.set(0, Some("zero".to_string()));
+        map.get_mut(0).unwrap().push('!');
+
+        assert_eq!(map.get(0), Some(&"zero!".to_string()));
+        assert_eq!(map.get_mut(1), None);
+    }
+
+    #[test]
+    fn test_get_never_written_does_not_write() {
+        let mut map: IndexMap<String> = IndexMap::new(b"test".to_vec());
+
+        assert_eq!(map.get(7), None);
+        map.flush();
+
+        assert!(storage_read(&lookup_key(b"test", 7)).is_none());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut map: IndexMap<String> = IndexMap::new(b"test".to_vec());
+
+        map.set(0, Some("zero".to_string()));
+        map.flush();
+
+        assert_eq!(map.remove(0), Some("zero".to_string()));
+        assert_eq!(map.remove(0), None);
+        assert!(!map.contains(0));
+    }
+
+    #[test]
+    fn test_persistence() {
+        let mut map: IndexMap<String> = IndexMap::new(b"test".to_vec());
+
+        map.set(3, Some("three".to_string()));
+        map.flush();
+
+        let stored = storage_read(&lookup_key(b"test", 3)).unwrap();
+        assert_eq!(String::try_from_slice(&stored).unwrap(), "three");
+
+        // A value set to `None` is removed from the storage on flush
+        map.set(3, None);
+        map.flush();
+        assert!(storage_read(&lookup_key(b"test", 3)).is_none());
+    }
+
+    #[test]
+    fn test_reload_from_storage() {
+        {
+            let mut map: IndexMap<String> = IndexMap::new(b"test".to_vec());
+            map.set(1, Some("one".to_string()));
+        }
+
+        // The value was flushed on drop
+        let map: IndexMap<String> = IndexMap::new(b"test".to_vec());
+        assert_eq!(map.get(1), Some(&"one".to_string()));
+    }
+
+    #[test]
+    fn test_flush_with_stats() {
+        let mut map: IndexMap<String> = IndexMap::new(b"test".to_vec());
+
+        map.set(0, Some("zero".to_string()));
+        map.set(1, Some("one".to_string()));
+        map.set(2, Some("two".to_string()));
+        assert_eq!(
+            map.flush_with_stats(),
+            FlushStats {
+                writes: 3,
+                removes: 0
+            }
+        );
+
+        // Reading doesn't modify the entries
+        assert_eq!(map.get(0), Some(&"zero".to_string()));
+        map.get_mut(1).unwrap().push('!');
+        map.remove(2);
+        map.set(3, Some("three".to_string()));
+        assert_eq!(
+            map.flush_with_stats(),
+            FlushStats {
+                writes: 2,
+                removes: 1
+            }
+        );
+
+        assert_eq!(map.flush_with_stats(), FlushStats::default());
+    }
 }
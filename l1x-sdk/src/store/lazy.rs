@@ -0,0 +1,166 @@
+//! A persistent value that is loaded lazily from the storage.
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use super::LazyOption;
+
+const ERR_NOT_INITIALIZED: &str = "Lazy value is not initialized";
+
+/// A persistent value that is stored under the `prefix` key.
+///
+/// Unlike [`LazyOption`], the value always exists after [`Self::new`] is called. The value is not
+/// read from the storage until it's accessed for the first time, after that it's cached. This is
+/// useful for a configuration that is read by most of the methods but rarely changes.
+///
+/// All operations are cached. The cache is flushed in the following cases:
+///
+/// * [`Self::flush`] method is called
+/// * [`drop`] method is called
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct Lazy<T>
+where
+    T: BorshSerialize,
+{
+    inner: LazyOption<T>,
+}
+
+impl<T> Lazy<T>
+where
+    T: BorshSerialize,
+{
+    /// Creates a new value. Uses `prefix` as a unique storage key.
+    ///
+    /// `initial` is written to the storage on flush.
+    pub fn new(prefix: Vec<u8>, initial: T) -> Self {
+        Self {
+            inner: LazyOption::new(prefix, Some(initial)),
+        }
+    }
+
+    /// Sets the value. The previous value is not loaded from the storage.
+    pub fn set(&mut self, value: T) {
+        self.inner.set(Some(value))
+    }
+
+    /// Writes the cached value to the persistent storage if it was modified.
+    ///
+    /// # Panic
+    ///
+    /// Panics if serialization fails
+    pub fn flush(&mut self) {
+        self.inner.flush()
+    }
+}
+
+impl<T> Lazy<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    /// Returns a reference to the value.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the value has never been initialized
+    pub fn get(&self) -> &T {
+        self.inner
+            .get()
+            .as_ref()
+            .unwrap_or_else(|| crate::panic(ERR_NOT_INITIALIZED))
+    }
+
+    /// Returns a mutable reference to the value. The value is marked as modified.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the value has never been initialized
+    pub fn get_mut(&mut self) -> &mut T {
+        self.inner
+            .get_mut()
+            .as_mut()
+            .unwrap_or_else(|| crate::panic(ERR_NOT_INITIALIZED))
+    }
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::tests::*;
+    use super::*;
+
+    #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+    struct Config {
+        fee: u32,
+        name: String,
+    }
+
+    #[test]
+    fn test_new_and_get() {
+        let config = Lazy::new(
+            b"config".to_vec(),
+            Config {
+                fee: 10,
+                name: "a".to_string(),
+            },
+        );
+        assert_eq!(config.get().fee, 10);
+    }
+
+    #[test]
+    fn test_lazy_load_once() {
+        let mut config = Lazy::new(
+            b"config".to_vec(),
+            Config {
+                fee: 10,
+                name: "a".to_string(),
+            },
+        );
+        config.flush();
+
+        // Restore the value the same way it's restored as a part of the contract state
+        let config = Lazy::<Config>::try_from_slice(&config.try_to_vec().unwrap()).unwrap();
+        let reads = get_mock_storage_reads();
+
+        assert_eq!(config.get().fee, 10);
+        assert_eq!(config.get().name, "a");
+        assert_eq!(get_mock_storage_reads(), reads + 1);
+    }
+
+    #[test]
+    fn test_mutation_persistence() {
+        let mut config = Lazy::new(
+            b"config".to_vec(),
+            Config {
+                fee: 10,
+                name: "a".to_string(),
+            },
+        );
+        config.get_mut().fee = 20;
+        config.flush();
+
+        let stored = Config::try_from_slice(&storage_read(b"config").unwrap()).unwrap();
+        assert_eq!(stored.fee, 20);
+
+        config.set(Config {
+            fee: 30,
+            name: "b".to_string(),
+        });
+        config.flush();
+
+        let stored = Config::try_from_slice(&storage_read(b"config").unwrap()).unwrap();
+        assert_eq!(
+            stored,
+            Config {
+                fee: 30,
+                name: "b".to_string()
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_get_not_initialized() {
+        let uninitialized = LazyOption::<Config>::new(b"config".to_vec(), None);
+        let config = Lazy::<Config>::try_from_slice(&uninitialized.try_to_vec().unwrap()).unwrap();
+        config.get();
+    }
+}
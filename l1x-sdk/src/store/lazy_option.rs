@@ -0,0 +1,206 @@
+//! A persistent optional value that is loaded lazily from the storage.
+use borsh::{BorshDeserialize, BorshSerialize};
+use once_cell::unsync::OnceCell;
+
+use crate::{CacheEntry, EntryState};
+
+const ERR_ELEMENT_DESERIALIZATION: &str = "Cannot deserialize element";
+const ERR_ELEMENT_SERIALIZATION: &str = "Cannot serialize element";
+
+/// A persistent optional value that is stored under the `prefix` key.
+///
+/// The value is not read from the storage until it's accessed for the first time, after that it's
+/// cached. This is useful for large values that are not used by every method of a contract.
+///
+/// All operations are cached. The cache is flushed in the following cases:
+///
+/// * [`Self::flush`] method is called
+/// * [`drop`] method is called
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct LazyOption<T>
+where
+    T: BorshSerialize,
+{
+    prefix: Box<[u8]>,
+
+    #[borsh_skip]
+    cache: OnceCell<CacheEntry<T>>,
+}
+
+impl<T> LazyOption<T>
+where
+    T: BorshSerialize,
+{
+    /// Creates a new optional value. Uses `prefix` as a unique storage key.
+    ///
+    /// * If `value` is `None` then the value is loaded from the storage on the first access.
+    /// * If `value` is `Some(v)` then `v` is written to the storage on flush.
+    pub fn new(prefix: Vec<u8>, value: Option<T>) -> Self {
+        let cache = match value {
+            Some(value) => OnceCell::from(CacheEntry::new_modified(Some(value))),
+            None => OnceCell::new(),
+        };
+        Self {
+            prefix: prefix.into_boxed_slice(),
+            cache,
+        }
+    }
+
+    /// Sets the value. The previous value is not loaded from the storage.
+    ///
+    /// If `value` is `None` then the value is removed from the storage on flush.
+    pub fn set(&mut self, value: Option<T>) {
+        match self.cache.get_mut() {
+            Some(entry) => *entry.value_mut() = value,
+            None => {
+                let _ = self.cache.set(CacheEntry::new_modified(value));
+            }
+        }
+    }
+
+    /// Writes the cached value to the persistent storage if it was modified.
+    ///
+    /// # Panic
+    ///
+    /// Panics if serialization fails
+    pub fn flush(&mut self) {
+        if let Some(entry) = self.cache.get_mut() {
+            if entry.is_modified() {
+                match entry.value().as_ref() {
+                    Some(value) => {
+                        let buf = value
+                            .try_to_vec()
+                            .unwrap_or_else(|_| crate::panic(ERR_ELEMENT_SERIALIZATION));
+                        crate::storage_write(&self.prefix, &buf);
+                    }
+                    None => {
+                        crate::storage_remove(&self.prefix);
+                    }
+                }
+
+                entry.replace_state(EntryState::Cached);
+            }
+        }
+    }
+}
+
+impl<T> LazyOption<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    fn load(prefix: &[u8]) -> CacheEntry<T> {
+        let value = crate::storage_read(prefix).map(|bytes| {
+            T::try_from_slice(&bytes).unwrap_or_else(|_| crate::panic(ERR_ELEMENT_DESERIALIZATION))
+        });
+        CacheEntry::new_cached(value)
+    }
+
+    fn get_mut_inner(&mut self) -> &mut CacheEntry<T> {
+        let prefix = &self.prefix;
+        self.cache.get_or_init(|| Self::load(prefix));
+        self.cache.get_mut().unwrap_or_else(|| crate::abort())
+    }
+
+    /// Returns a reference to the value.
+    pub fn get(&self) -> &Option<T> {
+        self.cache.get_or_init(|| Self::load(&self.prefix)).value()
+    }
+
+    /// Returns a mutable reference to the value. The value is marked as modified.
+    pub fn get_mut(&mut self) -> &mut Option<T> {
+        self.get_mut_inner().value_mut()
+    }
+
+    /// Takes the value out, leaving `None` in its place.
+    pub fn take(&mut self) -> Option<T> {
+        self.get_mut_inner().replace(None)
+    }
+
+    /// Replaces the value with `value`, returning the old value.
+    pub fn replace(&mut self, value: T) -> Option<T> {
+        self.get_mut_inner().replace(Some(value))
+    }
+
+    /// Returns `true` if the value is present.
+    pub fn is_some(&self) -> bool {
+        self.get().is_some()
+    }
+
+    /// Returns `true` if the value is not present.
+    pub fn is_none(&self) -> bool {
+        self.get().is_none()
+    }
+}
+
+impl<T> Drop for LazyOption<T>
+where
+    T: BorshSerialize,
+{
+    fn drop(&mut self) {
+        self.flush()
+    }
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_new_does_not_read_storage() {
+        storage_write(b"meta", &"stored".to_string().try_to_vec().unwrap());
+        let reads = get_mock_storage_reads();
+
+        let value: LazyOption<String> = LazyOption::new(b"meta".to_vec(), None);
+        assert_eq!(get_mock_storage_reads(), reads);
+
+        // The value is read once on the first access and cached after that
+        assert_eq!(value.get(), &Some("stored".to_string()));
+        assert_eq!(get_mock_storage_reads(), reads + 1);
+        assert!(value.is_some());
+        assert_eq!(get_mock_storage_reads(), reads + 1);
+    }
+
+    #[test]
+    fn test_set_and_get() {
+        let mut value: LazyOption<String> = LazyOption::new(b"meta".to_vec(), None);
+        assert!(value.is_none());
+
+        value.set(Some("a".to_string()));
+        assert_eq!(value.get(), &Some("a".to_string()));
+
+        value.get_mut().as_mut().unwrap().push('b');
+        assert_eq!(value.get(), &Some("ab".to_string()));
+    }
+
+    #[test]
+    fn test_take_and_replace() {
+        let mut value = LazyOption::new(b"meta".to_vec(), Some("a".to_string()));
+
+        assert_eq!(value.replace("b".to_string()), Some("a".to_string()));
+        assert_eq!(value.take(), Some("b".to_string()));
+        assert_eq!(value.take(), None);
+        assert!(value.is_none());
+    }
+
+    #[test]
+    fn test_persistence() {
+        let mut value = LazyOption::new(b"meta".to_vec(), Some("a".to_string()));
+        value.flush();
+        assert_eq!(
+            storage_read(b"meta"),
+            Some("a".to_string().try_to_vec().unwrap())
+        );
+
+        value.take();
+        value.flush();
+        assert!(storage_read(b"meta").is_none());
+
+        // The value is written on drop
+        drop(LazyOption::new(b"meta".to_vec(), Some("c".to_string())));
+        let value: LazyOption<String> = LazyOption::new(b"meta".to_vec(), None);
+        assert_eq!(value.get(), &Some("c".to_string()));
+    }
+}
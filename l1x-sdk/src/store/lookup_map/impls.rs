@@ -33,3 +33,15 @@ where
             .unwrap_or_else(|| crate::panic("does not exist"))
     }
 }
+
+impl<K, V, Q> core::ops::IndexMut<&Q> for LookupMap<K, V>
+where
+    K: BorshSerialize + Ord + Borrow<Q>,
+    V: BorshSerialize + BorshDeserialize,
+    Q: ?Sized + BorshSerialize + ToOwned<Owned = K>,
+{
+    fn index_mut(&mut self, index: &Q) -> &mut Self::Output {
+        self.get_mut(index)
+            .unwrap_or_else(|| crate::panic("does not exist"))
+    }
+}
@@ -1,6 +1,7 @@
 //! An implementation of a map that stores its content directly on the persistent storage.
 mod impls;
 
+use super::FlushStats;
 use crate::utils::{EntryState, StableMap};
 use crate::CacheEntry;
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -16,6 +17,17 @@ const ERR_ELEMENT_SERIALIZATION: &str = "Cannot serialize element";
 ///
 /// * [`Self::flush`] method is called
 /// * [`drop`] method is called
+///
+/// Values can be collections themselves. Use [`Self::get_or_create_with`] or
+/// [`crate::store::nested_prefix`] to get a unique prefix for a nested collection.
+///
+/// # Keys
+///
+/// The storage key of an entry is the prefix followed by the Borsh serialized key, so the keys
+/// should have a fixed, canonical encoding: [`crate::types::Address`], integers, `String` and
+/// fixed-size byte arrays work well. Lookups take any `&Q` the key can be borrowed as, e.g.
+/// `map.get(&address)` for `LookupMap<Address, V>` or `map.get("alice")` for
+/// `LookupMap<String, V>`.
 #[derive(BorshSerialize, BorshDeserialize)]
 pub struct LookupMap<K, V>
 where
@@ -24,7 +36,7 @@ where
 {
     prefix: Box<[u8]>,
     /// Cache for loads and intermediate changes to the underlying vector.
-    /// The cached entries are wrapped in a [`Box`] to avoid existing pointers from being
+    /// [`StableMap`] allocates the cached entries separately to avoid existing pointers from being
     /// invalidated.
     #[borsh_skip]
     cache: StableMap<K, EntryAndHash<V>>,
@@ -136,6 +148,30 @@ where
         entry.value().as_ref()
     }
 
+    /// Returns a clone of the value corresponding to the key.
+    ///
+    /// If the map doesn't have the key present, returns `None`
+    pub fn get_cloned<Q>(&self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + BorshSerialize + ToOwned<Owned = K>,
+        V: Clone,
+    {
+        self.get(k).cloned()
+    }
+
+    /// Returns a clone of the value corresponding to the key.
+    ///
+    /// If the map doesn't have the key present, returns `V::default()` without inserting it.
+    pub fn get_or_default<Q>(&self, k: &Q) -> V
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + BorshSerialize + ToOwned<Owned = K>,
+        V: Default + Clone,
+    {
+        self.get_cloned(k).unwrap_or_default()
+    }
+
     /// Returns a mutable reference to the value corresponding to the key.
     ///
     /// If the map doesn't have the key present, returns `None`
@@ -186,6 +222,41 @@ where
         self.get_mut_inner(&k).replace(Some(v))
     }
 
+    /// Returns a mutable reference to the value corresponding to the key, inserting the value
+    /// returned by `f` if the map doesn't have the key present.
+    ///
+    /// Like [`Self::get_mut`], the value is written to the storage on flush whether or not it's
+    /// changed through the reference.
+    pub fn get_or_insert_with<F>(&mut self, k: K, f: F) -> &mut V
+    where
+        K: Clone,
+        F: FnOnce() -> V,
+    {
+        let entry = self.get_mut_inner(&k);
+        if entry.value().is_none() {
+            entry.replace(Some(f()));
+        }
+        entry.value_mut().as_mut().unwrap_or_else(|| crate::abort())
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key, creating the value with
+    /// `f` if the map doesn't have the key present.
+    ///
+    /// `f` receives a prefix that is unique for `k`, see [`crate::store::nested_prefix`]. Use it
+    /// to create a nested collection.
+    pub fn get_or_create_with<F>(&mut self, k: K, f: F) -> &mut V
+    where
+        K: Clone,
+        F: FnOnce(Vec<u8>) -> V,
+    {
+        let nested_prefix = crate::store::nested_prefix(&self.prefix, &k);
+        let entry = self.get_mut_inner(&k);
+        if entry.value().is_none() {
+            entry.replace(Some(f(nested_prefix)));
+        }
+        entry.value_mut().as_mut().unwrap_or_else(|| crate::abort())
+    }
+
     /// Removes a key from the map, returning the value at the key if the key was previously in the map.
     pub fn remove(&mut self, k: K) -> Option<V>
     where
@@ -195,12 +266,39 @@ where
     }
 
     /// Returns true if the map contains a value for the specified key.
+    ///
+    /// If the key isn't cached, checks the storage with [`crate::storage_has_key`], so the value is
+    /// neither loaded into the contract memory nor deserialized.
     pub fn contains_key<Q: ?Sized>(&self, k: &Q) -> bool
     where
         K: Borrow<Q>,
         Q: BorshSerialize + ToOwned<Owned = K>,
     {
-        self.get(k).is_some()
+        let cached = self
+            .cache
+            .map_existing(&k.to_owned(), |entry| {
+                entry.value.get().map(|v| v.value().is_some())
+            })
+            .flatten();
+        match cached {
+            Some(present) => present,
+            None => crate::storage_has_key(&to_key(&self.prefix, k, &mut Vec::new())),
+        }
+    }
+
+    /// Removes the given keys from the map and clears the cache.
+    ///
+    /// The map doesn't track its keys, so the caller has to provide all the keys that should be
+    /// removed. The changes are flushed to the persistent storage immediately.
+    pub fn clear<I>(&mut self, keys: I)
+    where
+        I: IntoIterator<Item = K>,
+    {
+        for key in keys {
+            self.set(key, None);
+        }
+        self.flush();
+        self.cache.clear();
     }
 
     /// Writes the cached operations to the persistent storage.
@@ -209,8 +307,19 @@ where
     ///
     /// Panics if serialization fails
     pub fn flush(&mut self) {
+        self.flush_with_stats();
+    }
+
+    /// Writes the cached operations to the persistent storage like [`Self::flush`] and returns the
+    /// number of issued writes and removes.
+    ///
+    /// # Panic
+    ///
+    /// Panics if serialization fails
+    pub fn flush_with_stats(&mut self) -> FlushStats {
+        let mut stats = FlushStats::default();
         let mut buf = Vec::new();
-        for (k, v) in self.cache.inner().iter_mut() {
+        for (k, v) in self.cache.iter_mut() {
             if let Some(val) = v.value.get_mut() {
                 if val.is_modified() {
                     let prefix = &self.prefix;
@@ -224,9 +333,11 @@ where
                             BorshSerialize::serialize(modified, &mut buf)
                                 .unwrap_or_else(|_| crate::panic(ERR_ELEMENT_SERIALIZATION));
                             crate::storage_write(key.as_ref(), &buf);
+                            stats.writes += 1;
                         }
                         None => {
                             crate::storage_remove(key.as_ref());
+                            stats.removes += 1;
                         }
                     }
 
@@ -236,6 +347,7 @@ where
                 }
             }
         }
+        stats
     }
 }
 
@@ -245,6 +357,7 @@ where
 mod tests {
     use super::super::super::tests::*;
     use super::*;
+    use crate::types::Address;
     use borsh::{BorshDeserialize, BorshSerialize};
 
     #[derive(BorshSerialize, BorshDeserialize, Ord, PartialOrd, Eq, PartialEq, Clone, Debug)]
@@ -424,6 +537,48 @@ mod tests {
         assert_eq!(value, None);
     }
 
+    #[test]
+    fn test_clear() {
+        let mut map: LookupMap<TestKey, TestValue> = LookupMap::new(b"test".to_vec());
+
+        for i in 1..=3 {
+            map.insert(TestKey(i), TestValue(i * 10));
+        }
+        map.insert(TestKey(4), TestValue(40));
+        map.flush();
+
+        map.clear([TestKey(1), TestKey(2), TestKey(3)]);
+        assert!(map.cache.is_empty());
+
+        for i in 1..=3 {
+            assert_eq!(map.get(&TestKey(i)), None);
+            assert!(storage_read(&to_key(b"test", &TestKey(i), &mut Vec::new())).is_none());
+        }
+
+        // Keys that were not passed are kept
+        assert_eq!(map.get(&TestKey(4)), Some(&TestValue(40)));
+    }
+
+    #[test]
+    fn test_get_or_create_with_nested_vectors() {
+        use crate::store::Vector;
+
+        {
+            let mut map: LookupMap<u32, Vector<u32>> = LookupMap::new(b"test".to_vec());
+            map.get_or_create_with(1, Vector::new).push(10);
+            map.get_or_create_with(2, Vector::new).push(20);
+            map.get_or_create_with(1, Vector::new).push(11);
+        }
+
+        let map: LookupMap<u32, Vector<u32>> = LookupMap::new(b"test".to_vec());
+        let first = map.get(&1).unwrap();
+        let second = map.get(&2).unwrap();
+
+        assert_ne!(first.values.prefix, second.values.prefix);
+        assert_eq!(first.iter().copied().collect::<Vec<_>>(), vec![10, 11]);
+        assert_eq!(second.iter().copied().collect::<Vec<_>>(), vec![20]);
+    }
+
     #[test]
     fn test_contains_key() {
         let mut map = LookupMap::new(b"mymap".to_vec());
@@ -439,4 +594,134 @@ mod tests {
         map.remove(1);
         assert!(!map.contains_key(&1));
     }
+
+    #[test]
+    fn test_contains_key_does_not_read_value() {
+        {
+            let mut map = LookupMap::new(b"mymap".to_vec());
+            map.insert(1, "one".repeat(1000));
+        }
+
+        let map: LookupMap<i32, String> = LookupMap::new(b"mymap".to_vec());
+        let reads = get_mock_storage_reads();
+
+        assert!(map.contains_key(&1));
+        assert!(!map.contains_key(&2));
+        assert_eq!(get_mock_storage_reads(), reads);
+
+        // Cached entries are used as is
+        assert_eq!(map.get(&1).map(String::len), Some(3000));
+        assert!(map.contains_key(&1));
+        assert_eq!(get_mock_storage_reads(), reads + 1);
+    }
+
+    #[test]
+    fn test_get_cloned_and_get_or_default() {
+        let mut map: LookupMap<String, u64> = LookupMap::new(b"counters".to_vec());
+        map.insert("alice".to_string(), 3);
+
+        assert_eq!(map.get_cloned("alice"), Some(3));
+        assert_eq!(map.get_cloned("bob"), None);
+        assert_eq!(map.get_or_default("alice"), 3);
+        assert_eq!(map.get_or_default("bob"), 0);
+
+        // The default isn't inserted
+        map.flush();
+        assert!(!map.contains_key("bob"));
+        assert_eq!(map.get("bob"), None);
+    }
+
+    #[test]
+    fn test_index_mut() {
+        let mut map: LookupMap<String, u64> = LookupMap::new(b"counters".to_vec());
+        map.insert("alice".to_string(), 1);
+
+        map["alice"] += 1;
+
+        assert_eq!(map["alice"], 2);
+        map.flush();
+        assert_eq!(map.get("alice"), Some(&2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_mut_missing_key() {
+        let mut map: LookupMap<String, u64> = LookupMap::new(b"counters".to_vec());
+        map["alice"] += 1;
+    }
+
+    #[test]
+    fn test_flush_with_stats() {
+        let mut map: LookupMap<TestKey, TestValue> = LookupMap::new(b"test".to_vec());
+
+        for i in 0..3 {
+            map.insert(TestKey(i), TestValue(i));
+        }
+        assert_eq!(
+            map.flush_with_stats(),
+            FlushStats {
+                writes: 3,
+                removes: 0
+            }
+        );
+
+        map.insert(TestKey(0), TestValue(100));
+        map.remove(TestKey(1));
+        map.insert(TestKey(3), TestValue(3));
+        assert_eq!(map.get(&TestKey(2)), Some(&TestValue(2)));
+        assert_eq!(
+            map.flush_with_stats(),
+            FlushStats {
+                writes: 2,
+                removes: 1
+            }
+        );
+
+        assert_eq!(map.flush_with_stats(), FlushStats::default());
+    }
+
+    #[test]
+    fn test_get_or_insert_with() {
+        let mut map: LookupMap<TestKey, Vec<u32>> = LookupMap::new(b"test".to_vec());
+
+        map.get_or_insert_with(TestKey(1), Vec::new).push(1);
+        map.get_or_insert_with(TestKey(1), || unreachable!())
+            .push(2);
+        assert_eq!(map.get(&TestKey(1)), Some(&vec![1, 2]));
+
+        map.insert(TestKey(2), vec![10]);
+        map.get_or_insert_with(TestKey(2), || vec![0]).push(20);
+        map.flush();
+
+        // The values were persisted
+        let map: LookupMap<TestKey, Vec<u32>> = LookupMap::new(b"test".to_vec());
+        assert_eq!(map.get(&TestKey(1)), Some(&vec![1, 2]));
+        assert_eq!(map.get(&TestKey(2)), Some(&vec![10, 20]));
+    }
+
+    #[test]
+    fn test_address_keys() {
+        let alice = Address::from([1; 20]);
+        let bob = Address::from([2; 20]);
+
+        let mut balances: LookupMap<Address, u128> = LookupMap::new(b"b".to_vec());
+        balances.insert(alice, 100);
+        *balances.get_or_insert_with(bob, || 0) += 5;
+
+        assert_eq!(balances.get(&alice), Some(&100));
+        assert!(balances.contains_key(&bob));
+        assert_eq!(balances.remove(alice), Some(100));
+        assert!(!balances.contains_key(&alice));
+        drop(balances);
+
+        // The key is the prefix followed by the address bytes
+        let mut key = b"b".to_vec();
+        key.extend_from_slice(bob.as_bytes());
+        assert_eq!(storage_read(&key), Some(5u128.try_to_vec().unwrap()));
+
+        let balances: LookupMap<Address, u128> = LookupMap::new(b"b".to_vec());
+        assert_eq!(balances.get(&bob), Some(&5));
+        assert_eq!(balances.get(&alice), None);
+        assert!(!balances.contains_key(&Address::zero()));
+    }
 }
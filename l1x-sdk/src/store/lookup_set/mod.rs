@@ -1,7 +1,7 @@
 //! An implementation of a set that stores its content directly on the persistent storage.
 mod impls;
 
-use borsh::BorshSerialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use std::borrow::Borrow;
 
 use crate::store::LookupMap;
@@ -9,10 +9,16 @@ use crate::store::LookupMap;
 /// An implementation of a set that stores its content directly on the persistent storage.
 /// LookupSet is essentially a LookupMap where the key is the element
 /// and the value is a constant to signify its presence.
+///
+/// The number of elements is kept in the Borsh state of the set, so [`Self::len`] is only accurate
+/// if the set is persisted as a part of the contract state. A set reopened with [`Self::new`] over
+/// existing keys only counts the changes made since then, see [`Self::len`].
+#[derive(BorshSerialize, BorshDeserialize)]
 pub struct LookupSet<K>
 where
     K: BorshSerialize + Ord,
 {
+    len: u32,
     // We can use any type for V, such as a single byte, because we only care about the key.
     map: LookupMap<K, ()>,
 }
@@ -24,6 +30,7 @@ where
     /// Creates a new set. Uses `prefix` as a unique prefix for keys.
     pub fn new(prefix: Vec<u8>) -> Self {
         Self {
+            len: 0,
             map: LookupMap::new(prefix),
         }
     }
@@ -51,7 +58,11 @@ where
     where
         K: Clone,
     {
-        self.map.insert(k, ()).is_none()
+        let inserted = self.map.insert(k, ()).is_none();
+        if inserted {
+            self.len += 1;
+        }
+        inserted
     }
 
     /// Removes a value from the set. Returns whether the value was present in the set.
@@ -59,7 +70,54 @@ where
     where
         K: Clone,
     {
-        self.map.remove(k).is_some()
+        let removed = self.map.remove(k).is_some();
+        if removed {
+            // The element may predate the count if the set was reopened with `new`
+            self.len = self.len.saturating_sub(1);
+        }
+        removed
+    }
+
+    /// Adds all the values of `iter` to the set with [`Self::insert`].
+    ///
+    /// Returns the number of newly inserted values.
+    pub fn insert_many<I>(&mut self, iter: I) -> u32
+    where
+        I: IntoIterator<Item = K>,
+        K: Clone,
+    {
+        iter.into_iter()
+            .map(|k| self.insert(k))
+            .filter(|&changed| changed)
+            .count() as u32
+    }
+
+    /// Removes all the values of `iter` from the set with [`Self::remove`].
+    ///
+    /// Returns the number of values that were present in the set.
+    pub fn remove_many<I>(&mut self, iter: I) -> u32
+    where
+        I: IntoIterator<Item = K>,
+        K: Clone,
+    {
+        iter.into_iter()
+            .map(|k| self.remove(k))
+            .filter(|&changed| changed)
+            .count() as u32
+    }
+
+    /// Returns the number of elements in the set.
+    ///
+    /// The count is tracked since the set was created with [`Self::new`] or deserialized. If it
+    /// was created with [`Self::new`] over keys stored earlier, those keys aren't counted and
+    /// removing them never takes the count below zero.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns `true` if the set contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
     }
 
     /// Returns true if the set contains a value.
@@ -199,4 +257,93 @@ mod tests {
         assert_eq!(lookup_set.remove(40), false);
         assert_eq!(lookup_set.contains(&40), false);
     }
+
+    #[test]
+    fn test_contains_does_not_read_value() {
+        {
+            let mut set: LookupSet<TestValue> = LookupSet::new(b"test".to_vec());
+            set.insert(TestValue(10));
+        }
+
+        let set: LookupSet<TestValue> = LookupSet::new(b"test".to_vec());
+        let reads = crate::tests::get_mock_storage_reads();
+
+        assert!(set.contains(&TestValue(10)));
+        assert!(!set.contains(&TestValue(20)));
+        assert_eq!(crate::tests::get_mock_storage_reads(), reads);
+    }
+
+    #[test]
+    fn test_len() {
+        let mut set: LookupSet<TestValue> = LookupSet::new(b"test".to_vec());
+        assert!(set.is_empty());
+
+        set.insert(TestValue(1));
+        set.insert(TestValue(2));
+        set.insert(TestValue(1));
+        assert_eq!(set.len(), 2);
+
+        assert!(!set.remove(TestValue(3)));
+        assert_eq!(set.len(), 2);
+
+        set.remove(TestValue(1));
+        set.remove(TestValue(1));
+        assert_eq!(set.len(), 1);
+        assert!(!set.is_empty());
+    }
+
+    #[test]
+    fn test_len_persistence() {
+        let mut set: LookupSet<TestValue> = LookupSet::new(b"test".to_vec());
+        set.insert(TestValue(1));
+        set.insert(TestValue(2));
+        let state = set.try_to_vec().unwrap();
+        drop(set);
+
+        let mut set = LookupSet::<TestValue>::try_from_slice(&state).unwrap();
+        assert_eq!(set.len(), 2);
+        assert!(!set.insert(TestValue(2)));
+        assert_eq!(set.len(), 2);
+        assert!(set.remove(TestValue(2)));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_insert_many_and_remove_many() {
+        let mut set: LookupSet<TestValue> = LookupSet::new(b"test".to_vec());
+
+        assert_eq!(set.insert_many((0..5).map(TestValue)), 5);
+        // 3 and 4 are already present, 5 is repeated
+        assert_eq!(set.insert_many([3, 4, 5, 5, 6].map(TestValue)), 2);
+        assert_eq!(set.len(), 7);
+
+        // 7 and 8 are absent, 0 is repeated
+        assert_eq!(set.remove_many([0, 0, 1, 7, 8].map(TestValue)), 2);
+        assert_eq!(set.len(), 5);
+        assert!(!set.contains(&TestValue(0)));
+        assert!(set.contains(&TestValue(6)));
+
+        assert_eq!(set.insert_many([]), 0);
+        assert_eq!(set.remove_many([]), 0);
+    }
+
+    #[test]
+    fn test_remove_after_reopen() {
+        let mut set: LookupSet<TestValue> = LookupSet::new(b"test".to_vec());
+        set.insert(TestValue(1));
+        set.insert(TestValue(2));
+        drop(set);
+
+        let mut set: LookupSet<TestValue> = LookupSet::new(b"test".to_vec());
+        assert!(set.contains(&TestValue(1)));
+        assert_eq!(set.len(), 0);
+
+        assert!(set.remove(TestValue(1)));
+        assert_eq!(set.len(), 0);
+        assert!(set.is_empty());
+        assert!(!set.contains(&TestValue(1)));
+
+        assert!(set.insert(TestValue(3)));
+        assert_eq!(set.len(), 1);
+    }
 }
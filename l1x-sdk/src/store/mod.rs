@@ -3,6 +3,14 @@
 //! These collections are more scalable versions of [`std::collections`] when used as contract
 //! state because it allows values to be lazily loaded and stored based on what is actually
 //! interacted with.
+//!
+//! # Enumerating keys
+//!
+//! The VM can only access the storage by exact keys: there is no host function to iterate over
+//! the keys under a prefix, so the storage can't be scanned. [`LookupMap`] and [`LookupSet`]
+//! therefore can't be enumerated or cleared without knowing their keys. Use [`UnorderedSet`] or
+//! [`TreeMap`], which keep their own index of keys, when the contents have to be enumerated, e.g.
+//! for migrations or to verify that no orphaned keys are left behind.
 pub mod vec;
 pub use self::vec::Vector;
 
@@ -12,5 +20,114 @@ pub use self::lookup_set::LookupSet;
 pub mod lookup_map;
 pub use self::lookup_map::LookupMap;
 
-mod index_map;
-pub(crate) use self::index_map::IndexMap;
+pub mod unordered_set;
+pub use self::unordered_set::UnorderedSet;
+
+pub mod tree_map;
+pub use self::tree_map::TreeMap;
+
+pub mod deque;
+pub use self::deque::Deque;
+
+pub mod lazy_option;
+pub use self::lazy_option::LazyOption;
+
+pub mod lazy;
+pub use self::lazy::Lazy;
+
+pub mod index_map;
+pub use self::index_map::IndexMap;
+
+pub mod ring_buffer;
+pub use self::ring_buffer::RingBuffer;
+
+use borsh::BorshSerialize;
+pub use l1x_sdk_macros::StorageKey;
+
+/// Number of storage operations issued by a `flush_with_stats` call of a collection.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FlushStats {
+    /// Number of values written to the storage.
+    pub writes: u32,
+    /// Number of values removed from the storage.
+    pub removes: u32,
+}
+
+/// Separates the key of the parent collection from the keys of a nested collection.
+const NESTED_PREFIX_SEPARATOR: u8 = b'/';
+
+/// Returns a prefix for a collection nested into the element `key` of a parent collection.
+///
+/// The prefix is `parent_prefix ++ borsh(key) ++ b"/"`. Borsh encodings of keys are
+/// self-delimiting, so nested collections of different elements never share storage keys with each
+/// other or with the elements of the parent collection.
+pub fn nested_prefix<Q>(parent_prefix: &[u8], key: &Q) -> Vec<u8>
+where
+    Q: ?Sized + BorshSerialize,
+{
+    let mut prefix = parent_prefix.to_vec();
+    key.serialize(&mut prefix)
+        .unwrap_or_else(|_| crate::abort());
+    prefix.push(NESTED_PREFIX_SEPARATOR);
+    prefix
+}
+
+/// Names the storage prefix of a top level collection.
+///
+/// Hand-picked prefixes are easy to reuse by mistake, and two collections sharing a prefix
+/// overwrite each other's values. Declare an enum with a variant per collection instead and derive
+/// this trait, which assigns each variant a distinct byte:
+///
+/// ```ignore
+/// use l1x_sdk::store::{prefix_for, LookupMap, StorageKey, Vector};
+///
+/// #[derive(StorageKey)]
+/// enum Keys {
+///     Balances,
+///     Holders,
+/// }
+///
+/// let balances: LookupMap<u64, u128> = LookupMap::new(prefix_for(Keys::Balances));
+/// let holders: Vector<u64> = Vector::new(prefix_for(Keys::Holders));
+/// ```
+///
+/// Use [`nested_prefix`] with the returned prefix for collections nested into other collections.
+pub trait StorageKey {
+    /// The byte that prefixes the keys of the collection
+    fn prefix_byte(&self) -> u8;
+}
+
+/// Returns the prefix of the collection named by `key`.
+pub fn prefix_for(key: impl StorageKey) -> Vec<u8> {
+    vec![key.prefix_byte()]
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(StorageKey)]
+    enum Keys {
+        Balances,
+        Holders,
+        Allowances,
+    }
+
+    #[test]
+    fn test_prefix_for() {
+        let prefixes = [
+            prefix_for(Keys::Balances),
+            prefix_for(Keys::Holders),
+            prefix_for(Keys::Allowances),
+        ];
+        assert_eq!(prefixes, [vec![0], vec![1], vec![2]]);
+
+        let balances: LookupMap<u64, u128> = LookupMap::new(prefix_for(Keys::Balances));
+        let mut holders: Vector<u64> = Vector::new(prefix_for(Keys::Holders));
+        holders.push(7);
+        holders.flush();
+        assert_eq!(balances.get(&7), None);
+    }
+}
@@ -0,0 +1,227 @@
+//! A fixed-capacity buffer that stores its content to the persistent storage and evicts the
+//! oldest element when full.
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use super::IndexMap;
+
+const ERR_ZERO_CAPACITY: &str = "RingBuffer capacity must be greater than zero";
+
+/// A fixed-capacity buffer that stores its content to the persistent storage.
+/// Uses the following map: slot -> element.
+///
+/// The buffer keeps a `head` cursor pointing to the oldest element and the number of elements.
+/// Once the buffer holds `capacity` elements, [`Self::push`] overwrites the oldest one, so the
+/// buffer keeps the last `capacity` pushed elements, e.g. the latest prices. A push writes a single
+/// storage key and never reads the evicted element.
+///
+/// All operations are cached. The cache is flushed in the following cases:
+///
+/// * [`Self::flush`] method is called
+/// * [`drop`] method is called
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct RingBuffer<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    capacity: u32,
+    head: u32,
+    len: u32,
+    values: IndexMap<T>,
+}
+
+impl<T> RingBuffer<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    /// Creates a new empty buffer that holds up to `capacity` elements. Uses `prefix` as a unique
+    /// prefix for slots.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(prefix: Vec<u8>, capacity: u32) -> Self {
+        if capacity == 0 {
+            crate::panic(ERR_ZERO_CAPACITY);
+        }
+        Self {
+            capacity,
+            head: 0,
+            len: 0,
+            values: IndexMap::new(prefix),
+        }
+    }
+
+    /// Returns the maximum number of elements in the buffer.
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Returns the number of elements in the buffer.
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns `true` if the buffer contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the next [`Self::push`] evicts the oldest element.
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity
+    }
+
+    fn slot(&self, index: u32) -> u32 {
+        // Both are below `capacity`, so the sum fits into `u64`
+        ((self.head as u64 + index as u64) % self.capacity as u64) as u32
+    }
+
+    /// Appends an element as the newest one. If the buffer is full, the oldest element is
+    /// overwritten.
+    pub fn push(&mut self, element: T) {
+        if self.is_full() {
+            self.values.set(self.head, Some(element));
+            self.head = self.slot(1);
+        } else {
+            let slot = self.slot(self.len);
+            self.values.set(slot, Some(element));
+            self.len += 1;
+        }
+    }
+
+    /// Returns a reference to the element at `index` counting from the oldest one, or `None` if
+    /// out of bounds.
+    pub fn get(&self, index: u32) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        self.values.get(self.slot(index))
+    }
+
+    /// Returns a reference to the oldest element, or `None` if the buffer is empty.
+    pub fn oldest(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a reference to the newest element, or `None` if the buffer is empty.
+    pub fn newest(&self) -> Option<&T> {
+        self.len.checked_sub(1).and_then(|last| self.get(last))
+    }
+
+    /// Returns an iterator over the elements from the oldest to the newest.
+    ///
+    /// The elements are loaded lazily, so only the visited elements are read from the storage.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        (0..self.len).map(move |i| self.get(i).unwrap_or_else(|| crate::abort()))
+    }
+
+    /// Writes the cached operations to the persistent storage.
+    ///
+    /// # Panic
+    ///
+    /// Panics if serialization fails
+    pub fn flush(&mut self) {
+        self.values.flush();
+    }
+}
+
+impl<T> Extend<T> for RingBuffer<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iter {
+            self.push(item)
+        }
+    }
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::tests::*;
+    use super::*;
+
+    fn items(buffer: &RingBuffer<u32>) -> Vec<u32> {
+        buffer.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let buffer: RingBuffer<u32> = RingBuffer::new(b"r".to_vec(), 3);
+        assert_eq!(buffer.capacity(), 3);
+        assert!(buffer.is_empty());
+        assert!(!buffer.is_full());
+        assert_eq!(buffer.oldest(), None);
+        assert_eq!(buffer.newest(), None);
+        assert_eq!(items(&buffer), Vec::<u32>::new());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_capacity() {
+        let _: RingBuffer<u32> = RingBuffer::new(b"r".to_vec(), 0);
+    }
+
+    #[test]
+    fn test_push_evicts_oldest() {
+        let mut buffer: RingBuffer<u32> = RingBuffer::new(b"r".to_vec(), 3);
+
+        buffer.extend([1, 2]);
+        assert_eq!(items(&buffer), vec![1, 2]);
+        assert!(!buffer.is_full());
+
+        buffer.push(3);
+        assert!(buffer.is_full());
+        assert_eq!(items(&buffer), vec![1, 2, 3]);
+
+        for (value, expected) in [
+            (4, [2, 3, 4]),
+            (5, [3, 4, 5]),
+            (6, [4, 5, 6]),
+            (7, [5, 6, 7]),
+        ] {
+            buffer.push(value);
+            assert_eq!(items(&buffer), expected);
+            assert_eq!(buffer.len(), 3);
+        }
+        assert_eq!(buffer.oldest(), Some(&5));
+        assert_eq!(buffer.newest(), Some(&7));
+        assert_eq!(buffer.get(1), Some(&6));
+        assert_eq!(buffer.get(3), None);
+    }
+
+    #[test]
+    fn test_capacity_one() {
+        let mut buffer: RingBuffer<u32> = RingBuffer::new(b"r".to_vec(), 1);
+        buffer.extend([1, 2, 3]);
+        assert_eq!(items(&buffer), vec![3]);
+    }
+
+    #[test]
+    fn test_persistence() {
+        let mut buffer: RingBuffer<u32> = RingBuffer::new(b"r".to_vec(), 3);
+        buffer.extend(1..=5);
+        let state = buffer.try_to_vec().unwrap();
+        drop(buffer);
+
+        // Only the slots are stored, the evicted elements were overwritten
+        let slots = (0..3u32)
+            .map(|slot| {
+                let mut key = b"r".to_vec();
+                key.extend_from_slice(&slot.to_le_bytes());
+                u32::try_from_slice(&storage_read(&key).unwrap()).unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(slots, vec![4, 5, 3]);
+
+        let mut buffer = RingBuffer::<u32>::try_from_slice(&state).unwrap();
+        assert_eq!(items(&buffer), vec![3, 4, 5]);
+        buffer.push(6);
+        assert_eq!(items(&buffer), vec![4, 5, 6]);
+    }
+}
@@ -0,0 +1,18 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use super::TreeMap;
+
+impl<K, V> Extend<(K, V)> for TreeMap<K, V>
+where
+    K: BorshSerialize + BorshDeserialize + Ord + Clone,
+    V: BorshSerialize + BorshDeserialize,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
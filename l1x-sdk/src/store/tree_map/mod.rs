@@ -0,0 +1,399 @@
+//! An ordered implementation of a map that stores its content to the persistent storage.
+mod impls;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::ops::{Bound, RangeBounds};
+
+use crate::abort;
+use crate::store::{LookupMap, Vector};
+
+/// An ordered implementation of a map that stores its content to the persistent storage.
+///
+/// The keys are kept sorted in a [`Vector`] and the values are stored in a [`LookupMap`].
+/// Lookups by key are O(1) and cached like in [`LookupMap`], ordered queries use a binary search
+/// over the sorted keys. Inserting or removing a key shifts the keys that follow it, so these
+/// operations are O(n) storage writes in the worst case.
+///
+/// All operations are cached. The cache is flushed in the following cases:
+///
+/// * [`Self::flush`] method is called
+/// * [`drop`] method is called
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct TreeMap<K, V>
+where
+    K: BorshSerialize + BorshDeserialize + Ord,
+    V: BorshSerialize + BorshDeserialize,
+{
+    keys: Vector<K>,
+    values: LookupMap<K, V>,
+}
+
+impl<K, V> TreeMap<K, V>
+where
+    K: BorshSerialize + BorshDeserialize + Ord,
+    V: BorshSerialize + BorshDeserialize,
+{
+    /// Creates a new map. Uses `prefix` as a unique prefix for keys.
+    pub fn new(prefix: Vec<u8>) -> Self {
+        let mut keys_prefix = prefix.clone();
+        keys_prefix.push(b'k');
+        let mut values_prefix = prefix;
+        values_prefix.push(b'v');
+
+        Self {
+            keys: Vector::new(keys_prefix),
+            values: LookupMap::new(values_prefix),
+        }
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> u32 {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    fn key_at(&self, index: u32) -> &K {
+        self.keys.get(index).unwrap_or_else(|| abort())
+    }
+
+    /// Returns the index of the first key that is not less than `k`.
+    fn lower_bound(&self, k: &K) -> u32 {
+        let (mut lo, mut hi) = (0, self.keys.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.key_at(mid) < k {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+
+    /// Returns the index of the first key that is greater than `k`.
+    fn upper_bound(&self, k: &K) -> u32 {
+        let (mut lo, mut hi) = (0, self.keys.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.key_at(mid) <= k {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// If the map doesn't have the key present, returns `None`
+    pub fn get<Q>(&self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + BorshSerialize + ToOwned<Owned = K>,
+    {
+        self.values.get(k)
+    }
+
+    /// Returns true if the map contains a value for the specified key.
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + BorshSerialize + ToOwned<Owned = K>,
+    {
+        self.values.contains_key(k)
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, None is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old value is returned.
+    pub fn insert(&mut self, k: K, v: V) -> Option<V>
+    where
+        K: Clone,
+    {
+        if self.values.contains_key(&k) {
+            return self.values.insert(k, v);
+        }
+
+        // Shift the keys that are greater than `k` by one position to keep the order
+        let pos = self.lower_bound(&k);
+        let mut i = self.keys.len();
+        self.keys.push(k.clone());
+        while i > pos {
+            let prev = self.key_at(i - 1).clone();
+            self.keys.set(i, prev);
+            i -= 1;
+        }
+        self.keys.set(pos, k.clone());
+
+        self.values.insert(k, v)
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the map.
+    pub fn remove(&mut self, k: K) -> Option<V>
+    where
+        K: Clone,
+    {
+        if !self.values.contains_key(&k) {
+            return None;
+        }
+
+        // Shift the keys that are greater than `k` by one position back
+        let pos = self.lower_bound(&k);
+        let last_idx = self.keys.len() - 1;
+        for i in pos..last_idx {
+            let next = self.key_at(i + 1).clone();
+            self.keys.set(i, next);
+        }
+        self.keys.pop();
+
+        self.values.remove(k)
+    }
+
+    /// Returns the smallest key in the map, or `None` if the map is empty.
+    pub fn min(&self) -> Option<&K> {
+        self.keys.get(0)
+    }
+
+    /// Returns the largest key in the map, or `None` if the map is empty.
+    pub fn max(&self) -> Option<&K> {
+        self.keys
+            .len()
+            .checked_sub(1)
+            .and_then(|i| self.keys.get(i))
+    }
+
+    /// Returns the largest key that is less than or equal to `k`.
+    pub fn floor_key(&self, k: &K) -> Option<&K> {
+        self.upper_bound(k)
+            .checked_sub(1)
+            .and_then(|i| self.keys.get(i))
+    }
+
+    /// Returns the smallest key that is greater than or equal to `k`.
+    pub fn ceil_key(&self, k: &K) -> Option<&K> {
+        self.keys.get(self.lower_bound(k))
+    }
+
+    /// Returns an iterator over the key-value pairs of the map, sorted by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)>
+    where
+        K: Clone,
+    {
+        self.range(..)
+    }
+
+    /// Copies all the key-value pairs into a [`std::collections::BTreeMap`], e.g. to return them
+    /// from a view method as JSON.
+    ///
+    /// Every key and value is read from the storage and deserialized, so the gas cost grows
+    /// linearly with the length of the map. Use [`Self::range`] to return large maps in pages
+    /// instead.
+    pub fn to_std_map(&self) -> BTreeMap<K, V>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+
+    /// Returns an iterator over the key-value pairs within `range`, sorted by key.
+    ///
+    /// An empty iterator is returned if the start of the range is past its end.
+    pub fn range<R>(&self, range: R) -> impl Iterator<Item = (&K, &V)>
+    where
+        K: Clone,
+        R: RangeBounds<K>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(k) => self.lower_bound(k),
+            Bound::Excluded(k) => self.upper_bound(k),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(k) => self.upper_bound(k),
+            Bound::Excluded(k) => self.lower_bound(k),
+            Bound::Unbounded => self.keys.len(),
+        };
+
+        (start..end.max(start)).map(move |i| {
+            let key = self.key_at(i);
+            let value = self.values.get(key).unwrap_or_else(|| abort());
+            (key, value)
+        })
+    }
+
+    /// Writes the cached operations to the persistent storage.
+    ///
+    /// # Panic
+    ///
+    /// Panics if serialization fails
+    pub fn flush(&mut self) {
+        self.keys.flush();
+        self.values.flush();
+    }
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn assert_matches_oracle(map: &TreeMap<u32, String>, oracle: &BTreeMap<u32, String>) {
+        let items: Vec<(u32, String)> = map.iter().map(|(k, v)| (*k, v.clone())).collect();
+        let expected: Vec<(u32, String)> = oracle.iter().map(|(k, v)| (*k, v.clone())).collect();
+        assert_eq!(items, expected);
+        assert_eq!(map.len() as usize, oracle.len());
+    }
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut map: TreeMap<u32, String> = TreeMap::new(b"test".to_vec());
+        assert!(map.is_empty());
+
+        assert_eq!(map.insert(2, "two".to_string()), None);
+        assert_eq!(map.insert(1, "one".to_string()), None);
+        assert_eq!(map.insert(2, "TWO".to_string()), Some("two".to_string()));
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&2), Some(&"TWO".to_string()));
+        assert!(map.contains_key(&1));
+        assert!(!map.contains_key(&3));
+
+        assert_eq!(map.remove(1), Some("one".to_string()));
+        assert_eq!(map.remove(1), None);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get(&1), None);
+    }
+
+    #[test]
+    fn test_ordering_with_interleaved_inserts_and_removes() {
+        let mut map: TreeMap<u32, String> = TreeMap::new(b"test".to_vec());
+        let mut oracle = BTreeMap::new();
+
+        for k in [50, 10, 40, 20, 30, 60, 0, 35] {
+            map.insert(k, k.to_string());
+            oracle.insert(k, k.to_string());
+        }
+        assert_matches_oracle(&map, &oracle);
+
+        for k in [10, 60, 35, 99] {
+            assert_eq!(map.remove(k), oracle.remove(&k));
+        }
+        assert_matches_oracle(&map, &oracle);
+
+        for k in [5, 60, 45] {
+            map.insert(k, k.to_string());
+            oracle.insert(k, k.to_string());
+        }
+        assert_matches_oracle(&map, &oracle);
+    }
+
+    #[test]
+    fn test_range() {
+        let mut map: TreeMap<u32, String> = TreeMap::new(b"test".to_vec());
+        for k in [10, 20, 30, 40, 50] {
+            map.insert(k, k.to_string());
+        }
+
+        let keys = |r: Vec<(&u32, &String)>| r.into_iter().map(|(k, _)| *k).collect::<Vec<_>>();
+
+        assert_eq!(keys(map.range(20..40).collect()), vec![20, 30]);
+        assert_eq!(keys(map.range(20..=40).collect()), vec![20, 30, 40]);
+        assert_eq!(keys(map.range(15..45).collect()), vec![20, 30, 40]);
+        assert_eq!(keys(map.range(..30).collect()), vec![10, 20]);
+        assert_eq!(keys(map.range(..=30).collect()), vec![10, 20, 30]);
+        assert_eq!(keys(map.range(30..).collect()), vec![30, 40, 50]);
+        assert_eq!(
+            keys(
+                map.range((Bound::Excluded(20), Bound::Excluded(50)))
+                    .collect()
+            ),
+            vec![30, 40]
+        );
+        assert_eq!(keys(map.range(60..).collect()), Vec::<u32>::new());
+        assert_eq!(
+            keys(
+                map.range((Bound::Included(40), Bound::Excluded(20)))
+                    .collect()
+            ),
+            Vec::<u32>::new()
+        );
+
+        let values: Vec<&String> = map.range(50..=50).map(|(_, v)| v).collect();
+        assert_eq!(values, vec!["50"]);
+    }
+
+    #[test]
+    fn test_min_max_floor_ceil() {
+        let mut map: TreeMap<u32, String> = TreeMap::new(b"test".to_vec());
+        assert_eq!(map.min(), None);
+        assert_eq!(map.max(), None);
+        assert_eq!(map.floor_key(&10), None);
+        assert_eq!(map.ceil_key(&10), None);
+
+        for k in [30, 10, 20] {
+            map.insert(k, k.to_string());
+        }
+
+        assert_eq!(map.min(), Some(&10));
+        assert_eq!(map.max(), Some(&30));
+
+        assert_eq!(map.floor_key(&5), None);
+        assert_eq!(map.floor_key(&10), Some(&10));
+        assert_eq!(map.floor_key(&25), Some(&20));
+        assert_eq!(map.floor_key(&35), Some(&30));
+
+        assert_eq!(map.ceil_key(&5), Some(&10));
+        assert_eq!(map.ceil_key(&20), Some(&20));
+        assert_eq!(map.ceil_key(&25), Some(&30));
+        assert_eq!(map.ceil_key(&35), None);
+    }
+
+    #[test]
+    fn test_persistence() {
+        let mut map: TreeMap<u32, String> = TreeMap::new(b"test".to_vec());
+        for k in [3, 1, 2] {
+            map.insert(k, k.to_string());
+        }
+        map.remove(2);
+        map.flush();
+
+        let serialized = map.try_to_vec().unwrap();
+        let restored = TreeMap::<u32, String>::try_from_slice(&serialized).unwrap();
+
+        let items: Vec<(u32, String)> = restored.iter().map(|(k, v)| (*k, v.clone())).collect();
+        assert_eq!(items, vec![(1, "1".to_string()), (3, "3".to_string())]);
+    }
+
+    #[test]
+    fn test_to_std_map() {
+        let mut map: TreeMap<u32, String> = TreeMap::new(b"test".to_vec());
+        assert!(map.to_std_map().is_empty());
+
+        map.insert(2, "two".to_string());
+        map.insert(1, "one".to_string());
+        let std_map = map.to_std_map();
+        assert_eq!(
+            std_map,
+            BTreeMap::from([(1, "one".to_string()), (2, "two".to_string())])
+        );
+
+        let json = serde_json::to_string(&std_map).unwrap();
+        assert_eq!(json, r#"{"1":"one","2":"two"}"#);
+        assert_eq!(
+            serde_json::from_str::<BTreeMap<u32, String>>(&json).unwrap(),
+            std_map
+        );
+    }
+}
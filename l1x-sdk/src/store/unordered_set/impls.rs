@@ -0,0 +1,25 @@
+use super::UnorderedSet;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+impl<T> Extend<T> for UnorderedSet<T>
+where
+    T: BorshSerialize + BorshDeserialize + Ord + Clone,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        iter.into_iter().for_each(move |elem| {
+            self.insert(elem);
+        });
+    }
+}
+
+impl<T> Drop for UnorderedSet<T>
+where
+    T: BorshSerialize + BorshDeserialize + Ord,
+{
+    fn drop(&mut self) {
+        self.flush()
+    }
+}
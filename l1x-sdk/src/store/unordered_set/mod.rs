@@ -0,0 +1,229 @@
+//! An iterable implementation of a set that stores its content to the persistent storage.
+mod impls;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use std::borrow::Borrow;
+
+use crate::store::{vec, LookupMap, Vector};
+
+/// An iterable implementation of a set that stores its content to the persistent storage.
+///
+/// The elements are kept in a [`Vector`] and a [`LookupMap`] maps each element to its position
+/// in the vector. This allows the set to be enumerated while keeping `insert`, `remove` and
+/// `contains` O(1).
+///
+/// The order of elements is not preserved by [`Self::remove`].
+///
+/// All operations are cached. The cache is flushed in the following cases:
+///
+/// * [`Self::flush`] method is called
+/// * [`drop`] method is called
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct UnorderedSet<K>
+where
+    K: BorshSerialize + BorshDeserialize + Ord,
+{
+    elements: Vector<K>,
+    index: LookupMap<K, u32>,
+}
+
+impl<K> UnorderedSet<K>
+where
+    K: BorshSerialize + BorshDeserialize + Ord,
+{
+    /// Creates a new set. Uses `prefix` as a unique prefix for keys.
+    pub fn new(prefix: Vec<u8>) -> Self {
+        let mut elements_prefix = prefix.clone();
+        elements_prefix.push(b'v');
+        let mut index_prefix = prefix;
+        index_prefix.push(b'i');
+
+        Self {
+            elements: Vector::new(elements_prefix),
+            index: LookupMap::new(index_prefix),
+        }
+    }
+
+    /// Returns the number of elements in the set.
+    pub fn len(&self) -> u32 {
+        self.elements.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns an iterator over the elements of the set in arbitrary order.
+    pub fn iter(&self) -> vec::Iter<'_, K> {
+        self.elements.iter()
+    }
+
+    /// Returns true if the set contains a value.
+    pub fn contains<Q>(&self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + BorshSerialize + ToOwned<Owned = K>,
+    {
+        self.index.contains_key(k)
+    }
+
+    /// Adds a value to the set.
+    ///
+    /// Returns whether the value was newly inserted. That is:
+    ///
+    /// * If the set did not previously contain this value, true is returned.
+    /// * If the set already contained this value, false is returned.
+    pub fn insert(&mut self, k: K) -> bool
+    where
+        K: Clone,
+    {
+        if self.index.contains_key(&k) {
+            return false;
+        }
+
+        let idx = self.elements.len();
+        self.elements.push(k.clone());
+        self.index.insert(k, idx);
+        true
+    }
+
+    /// Removes a value from the set. Returns whether the value was present in the set.
+    ///
+    /// The removed element is replaced by the last element of the set, so this is O(1).
+    pub fn remove(&mut self, k: K) -> bool
+    where
+        K: Clone,
+    {
+        let idx = match self.index.remove(k) {
+            Some(idx) => idx,
+            None => return false,
+        };
+
+        self.elements.swap_remove(idx);
+        if let Some(moved) = self.elements.get(idx) {
+            // The last element took the place of the removed one, fix its position in the index
+            self.index.insert(moved.clone(), idx);
+        }
+
+        true
+    }
+
+    /// Writes the cached operations to the persistent storage.
+    ///
+    /// # Panic
+    ///
+    /// Panics if serialization fails
+    pub fn flush(&mut self) {
+        self.elements.flush();
+        self.index.flush();
+    }
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use borsh::{BorshDeserialize, BorshSerialize};
+    use std::collections::BTreeSet;
+
+    #[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct TestValue(String);
+
+    fn value(v: &str) -> TestValue {
+        TestValue(v.to_string())
+    }
+
+    #[test]
+    fn test_new() {
+        let set: UnorderedSet<TestValue> = UnorderedSet::new(b"test".to_vec());
+        assert_eq!(set.len(), 0);
+        assert!(set.is_empty());
+        assert_eq!(set.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_insert_and_contains() {
+        let mut set: UnorderedSet<TestValue> = UnorderedSet::new(b"test".to_vec());
+
+        assert!(set.insert(value("a")));
+        assert!(set.insert(value("b")));
+
+        // Inserting the same value again should return false
+        assert!(!set.insert(value("a")));
+
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&value("a")));
+        assert!(set.contains(&value("b")));
+        assert!(!set.contains(&value("c")));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut set: UnorderedSet<TestValue> = UnorderedSet::new(b"test".to_vec());
+
+        set.insert(value("a"));
+        set.insert(value("b"));
+        set.insert(value("c"));
+
+        // Remove the first element, the last one takes its place
+        assert!(set.remove(value("a")));
+        assert!(!set.contains(&value("a")));
+        assert_eq!(set.len(), 2);
+
+        // The relocated element can still be removed
+        assert!(set.remove(value("c")));
+        assert!(!set.contains(&value("c")));
+        assert_eq!(set.len(), 1);
+
+        // Try to remove a non-existent element
+        assert!(!set.remove(value("d")));
+        assert_eq!(set.len(), 1);
+
+        // Remove the last element
+        assert!(set.remove(value("b")));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut set: UnorderedSet<TestValue> = UnorderedSet::new(b"test".to_vec());
+        let mut oracle = BTreeSet::new();
+
+        for i in 0..10 {
+            set.insert(value(&i.to_string()));
+            oracle.insert(value(&i.to_string()));
+        }
+        for i in [0, 3, 9] {
+            set.remove(value(&i.to_string()));
+            oracle.remove(&value(&i.to_string()));
+        }
+
+        let elements: BTreeSet<TestValue> = set.iter().cloned().collect();
+        assert_eq!(elements, oracle);
+        assert_eq!(set.len() as usize, oracle.len());
+    }
+
+    #[test]
+    fn test_borsh_round_trip() {
+        let mut set: UnorderedSet<TestValue> = UnorderedSet::new(b"test".to_vec());
+
+        set.insert(value("a"));
+        set.insert(value("b"));
+        set.insert(value("c"));
+        set.remove(value("a"));
+        set.flush();
+
+        let serialized = set.try_to_vec().unwrap();
+        let restored = UnorderedSet::<TestValue>::try_from_slice(&serialized).unwrap();
+
+        assert_eq!(restored.len(), 2);
+        assert!(!restored.contains(&value("a")));
+        assert!(restored.contains(&value("b")));
+        assert!(restored.contains(&value("c")));
+
+        let elements: BTreeSet<TestValue> = restored.iter().cloned().collect();
+        assert_eq!(elements, BTreeSet::from([value("b"), value("c")]));
+    }
+}
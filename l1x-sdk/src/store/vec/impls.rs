@@ -1,6 +1,6 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 
-use super::{Vector, ERR_INDEX_OUT_OF_BOUNDS};
+use super::{Iter, IterMut, Vector, ERR_INDEX_OUT_OF_BOUNDS};
 
 impl<T> Drop for Vector<T>
 where
@@ -25,6 +25,30 @@ where
     }
 }
 
+impl<'a, T> IntoIterator for &'a Vector<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Vector<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 impl<T> core::ops::Index<u32> for Vector<T>
 where
     T: BorshSerialize + BorshDeserialize,
@@ -36,3 +60,13 @@ where
             .unwrap_or_else(|| crate::panic(ERR_INDEX_OUT_OF_BOUNDS))
     }
 }
+
+impl<T> core::ops::IndexMut<u32> for Vector<T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    fn index_mut(&mut self, index: u32) -> &mut Self::Output {
+        self.get_mut(index)
+            .unwrap_or_else(|| crate::panic(ERR_INDEX_OUT_OF_BOUNDS))
+    }
+}
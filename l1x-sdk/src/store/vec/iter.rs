@@ -0,0 +1,124 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use core::ops::Range;
+
+use super::Vector;
+use crate::abort;
+
+/// An iterator over references to the elements of a [`Vector`].
+///
+/// Elements are loaded lazily through the vector's cache.
+pub struct Iter<'a, T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    vec: &'a Vector<T>,
+    range: Range<u32>,
+}
+
+impl<'a, T> Iter<'a, T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    pub(super) fn new(vec: &'a Vector<T>) -> Self {
+        Self {
+            vec,
+            range: 0..vec.len(),
+        }
+    }
+
+    fn element(&self, index: u32) -> &'a T {
+        self.vec.get(index).unwrap_or_else(|| abort())
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|i| self.element(i))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.range.nth(n).map(|i| self.element(i))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|i| self.element(i))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> where T: BorshSerialize + BorshDeserialize {}
+
+/// An iterator over mutable references to the elements of a [`Vector`].
+///
+/// Elements are loaded lazily through the vector's cache. Every yielded element is marked as
+/// modified and is written to the storage on flush.
+pub struct IterMut<'a, T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    vec: &'a mut Vector<T>,
+    range: Range<u32>,
+}
+
+impl<'a, T> IterMut<'a, T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    pub(super) fn new(vec: &'a mut Vector<T>) -> Self {
+        let range = 0..vec.len();
+        Self { vec, range }
+    }
+
+    fn element(&mut self, index: u32) -> &'a mut T {
+        let element = self.vec.get_mut(index).unwrap_or_else(|| abort());
+        // SAFETY: `StableMap` allocates the cached entries separately, so the reference stays valid while
+        // the vector is borrowed. Each index is yielded only once, so the references don't alias.
+        unsafe { &mut *(element as *mut T) }
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.range.next()?;
+        Some(self.element(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let index = self.range.nth(n)?;
+        Some(self.element(index))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T>
+where
+    T: BorshSerialize + BorshDeserialize,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index = self.range.next_back()?;
+        Some(self.element(index))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> where T: BorshSerialize + BorshDeserialize {}
@@ -1,12 +1,16 @@
 //! An iterable implementation of vector that stores its content to the persitent storage.
 mod impls;
+mod iter;
 
 use crate::abort;
 
-use super::IndexMap;
+use super::{FlushStats, IndexMap};
 use borsh::{BorshDeserialize, BorshSerialize};
+pub use iter::{Iter, IterMut};
+use std::cmp::Ordering;
 
 const ERR_INDEX_OUT_OF_BOUNDS: &str = "Index out of bounds";
+const ERR_CAPACITY_OVERFLOW: &str = "Vector length exceeds u32::MAX";
 
 /// An iterable implementation of vector that stores its content to the persitent storage.
 /// Uses the following map: index -> element.
@@ -61,6 +65,19 @@ where
         }
     }
 
+    /// Creates a new vector from the elements of `iter`. Uses `prefix` as a unique prefix for indices.
+    ///
+    /// This is the counterpart of [`FromIterator`], which can't be implemented for [`Vector`]
+    /// because the vector requires a storage prefix.
+    pub fn from_iter_with_prefix<I>(prefix: Vec<u8>, iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut vec = Self::new(prefix);
+        vec.extend(iter);
+        vec
+    }
+
     /// Returns the number of elements in the vector, also referred to as its 'length'.
     pub fn len(&self) -> u32 {
         self.len
@@ -80,6 +97,16 @@ where
         self.values.flush();
     }
 
+    /// Writes the cached operations to the persistent storage like [`Self::flush`] and returns the
+    /// number of issued writes and removes.
+    ///
+    /// # Panic
+    ///
+    /// Panics if serialization fails
+    pub fn flush_with_stats(&mut self) -> FlushStats {
+        self.values.flush_with_stats()
+    }
+
     /// Inserts an element at `index`.
     ///
     /// # Panic
@@ -99,12 +126,27 @@ where
     ///
     /// Panics if the new length exceeds [`u32::MAX`].
     pub fn push(&mut self, element: T) {
+        if self.try_push(element).is_err() {
+            crate::panic(ERR_CAPACITY_OVERFLOW);
+        }
+    }
+
+    /// Appends an element to the back of a collection and returns its index.
+    ///
+    /// Returns the element back as `Err` if the new length exceeds [`u32::MAX`].
+    pub fn try_push(&mut self, element: T) -> Result<u32, T> {
         let last_idx = self.len();
-        self.len = self
-            .len
-            .checked_add(1)
-            .unwrap_or_else(|| crate::panic(ERR_INDEX_OUT_OF_BOUNDS));
-        self.set(last_idx, element)
+        match self.len.checked_add(1) {
+            Some(len) => self.len = len,
+            None => return Err(element),
+        }
+        self.set(last_idx, element);
+        Ok(last_idx)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_len_test(&mut self, len: u32) {
+        self.len = len;
     }
 
     /// Returns a reference to an element.
@@ -117,6 +159,62 @@ where
         self.values.get(index)
     }
 
+    /// Returns a reference to the first element, or `None` if the vector is empty.
+    pub fn first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a reference to the last element, or `None` if the vector is empty.
+    pub fn last(&self) -> Option<&T> {
+        self.get(self.len().checked_sub(1)?)
+    }
+
+    /// Returns `true` if the vector contains an element equal to `value`.
+    ///
+    /// This is O(n): the elements are loaded lazily until a match is found, so in the worst case
+    /// every element is read from the storage.
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|v| v == value)
+    }
+
+    /// Binary searches the vector for `value`, assuming the vector is sorted.
+    ///
+    /// See [`Self::binary_search_by`].
+    pub fn binary_search(&self, value: &T) -> Result<u32, u32>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|element| element.cmp(value))
+    }
+
+    /// Binary searches the vector with a comparator function, assuming the vector is sorted in the
+    /// order of `f`. `f` returns whether an element is less than, equal to or greater than the
+    /// target.
+    ///
+    /// Returns `Ok` with the index of a matching element, any of them if there are several, or
+    /// `Err` with the index where a matching element could be inserted while keeping the order.
+    /// The result is meaningless if the vector isn't sorted.
+    ///
+    /// Only the O(log n) visited elements are read from the storage.
+    pub fn binary_search_by<F>(&self, mut f: F) -> Result<u32, u32>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        let (mut lo, mut hi) = (0, self.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match f(self.get(mid).unwrap_or_else(|| abort())) {
+                Ordering::Less => lo = mid + 1,
+                Ordering::Greater => hi = mid,
+                Ordering::Equal => return Ok(mid),
+            }
+        }
+        Err(lo)
+    }
+
     /// Returns a mutable reference to an element.
     ///
     /// If given a position, returns a reference to the element at that position or `None` if out of bounds.
@@ -126,26 +224,33 @@ where
         }
         self.values.get_mut(index)
     }
-}
 
-impl<T> Vector<T>
-where
-    T: BorshSerialize + BorshDeserialize + Copy,
-{
     /// Removes the last element from a vector and returns it, or `None` if it is empty.
     pub fn pop(&mut self) -> Option<T> {
-        if self.len() == 0 {
-            return None;
-        }
+        let last_idx = self.len().checked_sub(1)?;
+        let last_value = self.values.get_mut_inner(last_idx).replace(None);
 
-        let last_idx = self.len() - 1;
-        let last_value = self.values.get(last_idx).copied();
+        self.len = last_idx;
 
-        self.values.set(last_idx, None);
+        last_value
+    }
 
-        self.len -= 1;
+    /// Swaps the elements at indices `a` and `b`. Does nothing if `a == b`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is out of bounds.
+    pub fn swap(&mut self, a: u32, b: u32) {
+        if a >= self.len() || b >= self.len() {
+            crate::panic(ERR_INDEX_OUT_OF_BOUNDS);
+        }
+        if a == b {
+            return;
+        }
 
-        last_value
+        let a_value = self.values.remove(a);
+        let b_value = self.values.get_mut_inner(b).replace(a_value);
+        self.values.set(a, b_value);
     }
 
     /// Removes an element from the vector and returns it.
@@ -162,16 +267,149 @@ where
             crate::panic(ERR_INDEX_OUT_OF_BOUNDS);
         }
 
-        let last_idx = self.len() - 1;
-        if last_idx == index {
-            self.pop().unwrap_or_else(|| abort())
+        let last_value = self.pop().unwrap_or_else(|| abort());
+        if index == self.len() {
+            last_value
         } else {
-            let elem = self.values.get(index).copied().unwrap_or_else(|| abort());
-            let last_elem = self.pop();
+            self.values
+                .get_mut_inner(index)
+                .replace(Some(last_value))
+                .unwrap_or_else(|| abort())
+        }
+    }
+
+    /// Inserts an element at `index`, moving the elements after it up by one.
+    ///
+    /// Every element after `index` is read and written again, so this is O(n) storage writes in
+    /// the worst case. Inserting at `len` is the same as [`Self::push`].
+    ///
+    /// # Panic
+    ///
+    /// Panics if `index > len` or if the new length exceeds [`u32::MAX`].
+    pub fn insert(&mut self, index: u32, value: T) {
+        if index > self.len() {
+            crate::panic(ERR_INDEX_OUT_OF_BOUNDS);
+        }
+        self.len = self
+            .len
+            .checked_add(1)
+            .unwrap_or_else(|| crate::panic(ERR_CAPACITY_OVERFLOW));
+        let mut carried = Some(value);
+        for i in index..self.len {
+            carried = self.values.get_mut_inner(i).replace(carried);
+        }
+    }
+
+    /// Inserts `value` into a sorted vector, keeping it sorted, and returns its index.
+    ///
+    /// The value is inserted after the elements equal to it, so elements that compare equal keep
+    /// their insertion order. The position is found with a binary search, but the elements after it
+    /// are moved up by one, which is O(n) storage writes in the worst case. The result is
+    /// meaningless if the vector isn't sorted.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the new length exceeds [`u32::MAX`].
+    pub fn insert_sorted(&mut self, value: T) -> u32
+    where
+        T: Ord,
+    {
+        let index = self
+            .binary_search_by(|element| match element.cmp(&value) {
+                Ordering::Greater => Ordering::Greater,
+                Ordering::Less | Ordering::Equal => Ordering::Less,
+            })
+            .unwrap_or_else(|index| index);
+        self.insert(index, value);
+        index
+    }
 
-            self.values.set(index, last_elem);
-            elem
+    /// Retains only the elements for which `f` returns `true`, preserving their order.
+    ///
+    /// The retained elements are moved toward the front and the storage keys past the new length
+    /// are removed on flush. Every element is read from the storage.
+    pub fn retain(&mut self, mut f: impl FnMut(&T) -> bool) {
+        let mut kept = 0;
+        for index in 0..self.len() {
+            let keep = f(self.values.get(index).unwrap_or_else(|| abort()));
+            if !keep {
+                self.values.set(index, None);
+                continue;
+            }
+            if index != kept {
+                let value = self.values.remove(index);
+                self.values.set(kept, value);
+            }
+            kept += 1;
         }
+        self.len = kept;
+    }
+
+    /// Shortens the vector to `new_len` elements. Does nothing if the vector is already shorter.
+    ///
+    /// The storage keys of the removed elements are removed on flush without reading the elements.
+    pub fn truncate(&mut self, new_len: u32) {
+        for index in new_len..self.len() {
+            self.values.set(index, None);
+        }
+        self.len = self.len.min(new_len);
+    }
+
+    /// Resizes the vector to `new_len` elements, either truncating it or appending clones of
+    /// `value`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `new_len` exceeds [`u32::MAX`] elements.
+    pub fn resize(&mut self, new_len: u32, value: T)
+    where
+        T: Clone,
+    {
+        if new_len <= self.len() {
+            self.truncate(new_len);
+            return;
+        }
+        for _ in self.len()..new_len {
+            self.push(value.clone());
+        }
+    }
+
+    /// Removes all elements from the vector and resets its length to zero.
+    ///
+    /// Every element is removed from the storage and the changes are flushed immediately.
+    pub fn clear(&mut self) {
+        for i in 0..self.len() {
+            self.values.set(i, None);
+        }
+        self.len = 0;
+        self.flush();
+    }
+
+    /// Returns an iterator over the vector.
+    ///
+    /// The elements are loaded lazily, so only the visited elements are read from the storage.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self)
+    }
+
+    /// Copies all the elements into a [`std::vec::Vec`], e.g. to return them from a view method as
+    /// JSON.
+    ///
+    /// Every element is read from the storage and deserialized, so the gas cost grows linearly with
+    /// the length of the vector. Use [`Self::iter`] with `skip`/`take` to return large vectors in
+    /// pages instead.
+    pub fn to_std_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    /// Returns an iterator over the vector that allows modifying each element.
+    ///
+    /// The elements are loaded lazily, so only the visited elements are read from the storage.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut::new(self)
     }
 }
 
@@ -290,6 +528,149 @@ mod tests {
         vector.swap_remove(1);
     }
 
+    #[test]
+    fn test_vector_iter() {
+        let mut vector: Vector<TestValue> = Vector::new(b"test".to_vec());
+        assert_eq!(vector.iter().next(), None);
+
+        vector.extend((1..=10).map(TestValue));
+
+        let sum: i32 = vector.iter().map(|v| v.0).sum();
+        assert_eq!(sum, 55);
+        assert_eq!(vector.iter().len(), 10);
+        assert_eq!(vector.iter().next_back(), Some(&TestValue(10)));
+        assert_eq!(vector.iter().nth(3), Some(&TestValue(4)));
+    }
+
+    #[test]
+    fn test_vector_iter_honors_len() {
+        let mut vector: Vector<TestValue> = Vector::new(b"test".to_vec());
+        vector.extend((0..3).map(TestValue));
+        vector.pop();
+
+        let values: Vec<&TestValue> = vector.iter().collect();
+        assert_eq!(values, vec![&TestValue(0), &TestValue(1)]);
+    }
+
+    #[test]
+    fn test_vector_iter_mut_persistence() {
+        let mut vector: Vector<TestValue> = Vector::new(b"test".to_vec());
+        vector.extend((0..5).map(TestValue));
+        vector.flush();
+
+        for value in vector.iter_mut() {
+            value.0 *= 10;
+        }
+        vector.flush();
+
+        for i in 0..5u32 {
+            let mut key = b"test".to_vec();
+            key.extend_from_slice(&i.to_le_bytes());
+            let stored = TestValue::try_from_slice(&storage_read(&key).unwrap()).unwrap();
+            assert_eq!(stored, TestValue(i as i32 * 10));
+        }
+    }
+
+    #[test]
+    fn test_vector_from_iter_with_prefix() {
+        let vector = Vector::from_iter_with_prefix(b"test".to_vec(), (0..100).map(TestValue));
+
+        assert_eq!(vector.len(), 100);
+        assert_eq!(vector.get(0), Some(&TestValue(0)));
+        assert_eq!(vector.get(42), Some(&TestValue(42)));
+        assert_eq!(vector.get(99), Some(&TestValue(99)));
+        assert_eq!(vector.get(100), None);
+    }
+
+    #[test]
+    fn test_vector_into_iter() {
+        let mut vector = Vector::from_iter_with_prefix(b"test".to_vec(), (0..3).map(TestValue));
+
+        for value in &mut vector {
+            value.0 += 1;
+        }
+
+        let mut values = Vec::new();
+        for value in &vector {
+            values.push(*value);
+        }
+        assert_eq!(values, vec![TestValue(1), TestValue(2), TestValue(3)]);
+    }
+
+    #[test]
+    fn test_vector_pop_non_copy() {
+        let mut vector: Vector<String> = Vector::new(b"test".to_vec());
+        vector.push("a".to_string());
+        vector.push("b".to_string());
+        vector.flush();
+
+        assert_eq!(vector.pop(), Some("b".to_string()));
+        assert_eq!(vector.pop(), Some("a".to_string()));
+        assert_eq!(vector.pop(), None);
+        vector.flush();
+
+        // The popped elements are removed from the storage
+        let mut key = b"test".to_vec();
+        key.extend_from_slice(&0u32.to_le_bytes());
+        assert!(storage_read(&key).is_none());
+    }
+
+    #[test]
+    fn test_vector_swap_remove_non_copy() {
+        #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug)]
+        struct Record {
+            name: String,
+            data: Vec<u8>,
+        }
+
+        let record = |name: &str| Record {
+            name: name.to_string(),
+            data: name.as_bytes().to_vec(),
+        };
+
+        let mut vector: Vector<Record> = Vector::new(b"test".to_vec());
+        vector.push(record("a"));
+        vector.push(record("b"));
+        vector.push(record("c"));
+
+        assert_eq!(vector.swap_remove(0), record("a"));
+        assert_eq!(vector.len(), 2);
+        assert_eq!(vector.get(0), Some(&record("c")));
+        assert_eq!(vector.get(1), Some(&record("b")));
+
+        assert_eq!(vector.swap_remove(1), record("b"));
+        assert_eq!(vector.len(), 1);
+        assert_eq!(vector.get(0), Some(&record("c")));
+    }
+
+    #[test]
+    fn test_vector_clear() {
+        let mut vector: Vector<TestValue> = Vector::new(b"test".to_vec());
+        vector.extend((0..5).map(TestValue));
+        vector.flush();
+
+        vector.clear();
+        assert!(vector.is_empty());
+        assert_eq!(vector.get(0), None);
+
+        // All storage keys are removed
+        for i in 0..5u32 {
+            let mut key = b"test".to_vec();
+            key.extend_from_slice(&i.to_le_bytes());
+            assert!(storage_read(&key).is_none());
+        }
+
+        // A subsequent push reuses index 0
+        vector.push(TestValue(42));
+        vector.flush();
+        assert_eq!(vector.len(), 1);
+
+        let mut key = b"test".to_vec();
+        key.extend_from_slice(&0u32.to_le_bytes());
+        let stored = TestValue::try_from_slice(&storage_read(&key).unwrap()).unwrap();
+        assert_eq!(stored, TestValue(42));
+    }
+
     #[test]
     fn test_push_persistence() {
         let mut vector: Vector<TestValue> = Vector::new(b"test".to_vec());
@@ -325,4 +706,374 @@ mod tests {
             TestValue::try_from_slice(&mut &*storage_read(&expected_key).unwrap()).unwrap();
         assert_eq!(written_value, TestValue(20));
     }
+
+    #[test]
+    fn test_flush_host_writes() {
+        let mut vector: Vector<TestValue> = Vector::new(b"bench".to_vec());
+        for i in 0..1000 {
+            vector.push(TestValue(i));
+        }
+
+        let writes = get_mock_storage_writes();
+        vector.flush();
+        // One write per modified element
+        assert_eq!(get_mock_storage_writes() - writes, 1000);
+
+        // Nothing is modified, so nothing is written
+        vector.flush();
+        assert_eq!(get_mock_storage_writes() - writes, 1000);
+
+        let mut key = b"bench".to_vec();
+        key.extend_from_slice(&999u32.to_le_bytes());
+        let last = TestValue::try_from_slice(&storage_read(&key).unwrap()).unwrap();
+        assert_eq!(last, TestValue(999));
+    }
+
+    #[test]
+    fn test_first_last_contains() {
+        let mut vector: Vector<TestValue> = Vector::new(b"test".to_vec());
+
+        assert_eq!(vector.first(), None);
+        assert_eq!(vector.last(), None);
+        assert!(!vector.contains(&TestValue(10)));
+
+        vector.push(TestValue(10));
+        assert_eq!(vector.first(), Some(&TestValue(10)));
+        assert_eq!(vector.last(), Some(&TestValue(10)));
+
+        vector.push(TestValue(20));
+        vector.push(TestValue(30));
+        assert_eq!(vector.first(), Some(&TestValue(10)));
+        assert_eq!(vector.last(), Some(&TestValue(30)));
+        assert!(vector.contains(&TestValue(20)));
+        assert!(!vector.contains(&TestValue(40)));
+
+        vector.pop();
+        assert_eq!(vector.last(), Some(&TestValue(20)));
+        assert!(!vector.contains(&TestValue(30)));
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut vector: Vector<TestValue> =
+            Vector::from_iter_with_prefix(b"test".to_vec(), (0..10).map(TestValue));
+        vector.flush();
+        let mut oracle: Vec<TestValue> = (0..10).map(TestValue).collect();
+
+        vector.retain(|v| v.0 % 2 == 0);
+        oracle.retain(|v| v.0 % 2 == 0);
+
+        assert_eq!(vector.len(), oracle.len() as u32);
+        assert_eq!(vector.iter().copied().collect::<Vec<_>>(), oracle);
+
+        vector.flush();
+        let key = |index: u32| {
+            let mut key = b"test".to_vec();
+            key.extend_from_slice(&index.to_le_bytes());
+            key
+        };
+        assert_eq!(
+            TestValue::try_from_slice(&storage_read(&key(4)).unwrap()).unwrap(),
+            TestValue(8)
+        );
+        assert!((5..10).all(|index| storage_read(&key(index)).is_none()));
+
+        vector.retain(|_| false);
+        assert!(vector.is_empty());
+        assert_eq!(vector.first(), None);
+    }
+
+    #[test]
+    fn test_truncate() {
+        let mut vector: Vector<TestValue> =
+            Vector::from_iter_with_prefix(b"test".to_vec(), (0..5).map(TestValue));
+        vector.flush();
+
+        vector.truncate(10);
+        assert_eq!(vector.len(), 5);
+
+        vector.truncate(2);
+        assert_eq!(vector.len(), 2);
+        assert_eq!(vector.last(), Some(&TestValue(1)));
+        assert_eq!(vector.get(2), None);
+
+        vector.flush();
+        let mut key = b"test".to_vec();
+        key.extend_from_slice(&2u32.to_le_bytes());
+        assert!(storage_read(&key).is_none());
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut vector: Vector<TestValue> = Vector::new(b"test".to_vec());
+
+        vector.resize(3, TestValue(7));
+        assert_eq!(
+            vector.iter().copied().collect::<Vec<_>>(),
+            vec![TestValue(7); 3]
+        );
+
+        vector.set(0, TestValue(1));
+        vector.resize(1, TestValue(0));
+        assert_eq!(
+            vector.iter().copied().collect::<Vec<_>>(),
+            vec![TestValue(1)]
+        );
+
+        vector.resize(2, TestValue(2));
+        assert_eq!(
+            vector.iter().copied().collect::<Vec<_>>(),
+            vec![TestValue(1), TestValue(2)]
+        );
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut vector: Vector<TestValue> =
+            Vector::from_iter_with_prefix(b"test".to_vec(), (0..3).map(TestValue));
+        vector.flush();
+
+        vector.swap(0, 2);
+        vector.swap(1, 1);
+        vector.flush();
+
+        let read = |index: u32| {
+            let mut key = b"test".to_vec();
+            key.extend_from_slice(&index.to_le_bytes());
+            TestValue::try_from_slice(&storage_read(&key).unwrap()).unwrap()
+        };
+        assert_eq!(read(0), TestValue(2));
+        assert_eq!(read(1), TestValue(1));
+        assert_eq!(read(2), TestValue(0));
+
+        let vector: Vector<TestValue> = Vector {
+            len: 3,
+            values: IndexMap::new(b"test".to_vec()),
+        };
+        assert_eq!(
+            vector.iter().copied().collect::<Vec<_>>(),
+            vec![TestValue(2), TestValue(1), TestValue(0)]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_swap_out_of_bounds() {
+        let mut vector: Vector<TestValue> = Vector::new(b"test".to_vec());
+        vector.push(TestValue(0));
+        vector.swap(0, 1);
+    }
+
+    #[test]
+    fn test_index_mut() {
+        let mut vector: Vector<TestValue> = Vector::new(b"test".to_vec());
+        vector.push(TestValue(1));
+
+        vector[0] = TestValue(2);
+        vector[0].0 += 1;
+
+        assert_eq!(vector[0], TestValue(3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_mut_out_of_bounds() {
+        let mut vector: Vector<TestValue> = Vector::new(b"test".to_vec());
+        vector[0] = TestValue(1);
+    }
+
+    #[test]
+    fn test_flush_with_stats() {
+        let mut vector: Vector<TestValue> = Vector::new(b"v".to_vec());
+
+        for i in 0..3 {
+            vector.push(TestValue(i));
+        }
+        assert_eq!(
+            vector.flush_with_stats(),
+            FlushStats {
+                writes: 3,
+                removes: 0
+            }
+        );
+
+        vector.set(0, TestValue(100));
+        vector.pop();
+        assert_eq!(
+            vector.flush_with_stats(),
+            FlushStats {
+                writes: 1,
+                removes: 1
+            }
+        );
+
+        assert_eq!(vector.flush_with_stats(), FlushStats::default());
+    }
+
+    #[test]
+    fn test_to_std_vec() {
+        let mut vector: Vector<u32> = Vector::new(b"v".to_vec());
+        assert_eq!(vector.to_std_vec(), Vec::<u32>::new());
+
+        vector.extend([1, 2, 3]);
+        let std_vec = vector.to_std_vec();
+        assert_eq!(std_vec, vec![1, 2, 3]);
+
+        let json = serde_json::to_string(&std_vec).unwrap();
+        assert_eq!(json, "[1,2,3]");
+        assert_eq!(serde_json::from_str::<Vec<u32>>(&json).unwrap(), std_vec);
+    }
+
+    #[test]
+    fn test_binary_search() {
+        let mut vector: Vector<u32> = Vector::new(b"v".to_vec());
+        assert_eq!(vector.binary_search(&1), Err(0));
+
+        vector.extend([10, 20, 30, 40, 50]);
+        assert_eq!(vector.binary_search(&10), Ok(0));
+        assert_eq!(vector.binary_search(&30), Ok(2));
+        assert_eq!(vector.binary_search(&50), Ok(4));
+
+        assert_eq!(vector.binary_search(&5), Err(0));
+        assert_eq!(vector.binary_search(&25), Err(2));
+        assert_eq!(vector.binary_search(&55), Err(5));
+
+        let std_vec = vector.to_std_vec();
+        for value in 0..60 {
+            assert_eq!(
+                vector.binary_search(&value),
+                std_vec
+                    .binary_search(&value)
+                    .map(|i| i as u32)
+                    .map_err(|i| i as u32)
+            );
+        }
+
+        // Sorted in descending order
+        let mut vector: Vector<u32> = Vector::new(b"d".to_vec());
+        vector.extend([3, 2, 1]);
+        assert_eq!(vector.binary_search_by(|v| 1.cmp(v)), Ok(2));
+        assert_eq!(vector.binary_search_by(|v| 0.cmp(v)), Err(3));
+    }
+
+    #[test]
+    fn test_binary_search_reads_lazily() {
+        let mut vector: Vector<u32> = Vector::new(b"v".to_vec());
+        vector.extend(0..1024);
+        let state = vector.try_to_vec().unwrap();
+        drop(vector);
+
+        let vector = Vector::<u32>::try_from_slice(&state).unwrap();
+        let reads = get_mock_storage_reads();
+        assert_eq!(vector.binary_search(&0), Ok(0));
+        assert!(get_mock_storage_reads() - reads <= 11);
+    }
+
+    #[test]
+    fn test_insert_sorted() {
+        let mut vector: Vector<u32> = Vector::new(b"v".to_vec());
+        let mut oracle = Vec::new();
+
+        for i in 0..200u32 {
+            let value = (i * 7919) % 61;
+            let index = vector.insert_sorted(value);
+            let expected = oracle.partition_point(|v| *v <= value);
+            oracle.insert(expected, value);
+
+            assert_eq!(index, expected as u32);
+            assert_eq!(vector.len(), oracle.len() as u32);
+        }
+        assert_eq!(vector.to_std_vec(), oracle);
+
+        // Persisted in order
+        vector.flush();
+        let state = vector.try_to_vec().unwrap();
+        drop(vector);
+        let vector = Vector::<u32>::try_from_slice(&state).unwrap();
+        assert_eq!(vector.to_std_vec(), oracle);
+    }
+
+    #[test]
+    fn test_insert_sorted_keeps_insertion_order_of_equal_elements() {
+        #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+        struct Score(u32, u8);
+
+        impl PartialEq for Score {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for Score {}
+        impl PartialOrd for Score {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Score {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let mut vector: Vector<Score> = Vector::new(b"v".to_vec());
+        assert_eq!(vector.insert_sorted(Score(5, 0)), 0);
+        assert_eq!(vector.insert_sorted(Score(1, 1)), 0);
+        assert_eq!(vector.insert_sorted(Score(5, 2)), 2);
+        assert_eq!(vector.insert_sorted(Score(9, 3)), 3);
+
+        let ids: Vec<u8> = vector.iter().map(|score| score.1).collect();
+        assert_eq!(ids, vec![1, 0, 2, 3]);
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut vector: Vector<u32> = Vector::new(b"v".to_vec());
+        let mut oracle = Vec::new();
+
+        for (index, value) in [(0, 1), (1, 2), (0, 3), (1, 4), (4, 5), (2, 6), (6, 7)] {
+            vector.insert(index, value);
+            oracle.insert(index as usize, value);
+            assert_eq!(vector.to_std_vec(), oracle);
+        }
+        assert_eq!(oracle, vec![3, 4, 6, 1, 2, 5, 7]);
+
+        vector.flush();
+        let state = vector.try_to_vec().unwrap();
+        drop(vector);
+        let vector = Vector::<u32>::try_from_slice(&state).unwrap();
+        assert_eq!(vector.to_std_vec(), oracle);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_out_of_bounds() {
+        let mut vector: Vector<u32> = Vector::new(b"v".to_vec());
+        vector.push(1);
+        vector.insert(2, 2);
+    }
+
+    #[test]
+    fn test_try_push() {
+        let mut vector: Vector<TestValue> = Vector::new(b"v".to_vec());
+        assert_eq!(vector.try_push(TestValue(1)), Ok(0));
+        assert_eq!(vector.try_push(TestValue(2)), Ok(1));
+        assert_eq!(vector.to_std_vec(), vec![TestValue(1), TestValue(2)]);
+
+        vector.set_len_test(u32::MAX - 1);
+        assert_eq!(vector.try_push(TestValue(3)), Ok(u32::MAX - 1));
+        assert_eq!(vector.len(), u32::MAX);
+        assert_eq!(vector.try_push(TestValue(4)), Err(TestValue(4)));
+        assert_eq!(vector.len(), u32::MAX);
+    }
+
+    #[test]
+    fn test_push_capacity_overflow() {
+        let mut vector: Vector<TestValue> = Vector::new(b"v".to_vec());
+        vector.set_len_test(u32::MAX);
+
+        let result = crate::utils::catch_panic(|| vector.push(TestValue(1)));
+        assert!(result.is_err());
+        assert_eq!(get_mock_msgs().pop().unwrap(), ERR_CAPACITY_OVERFLOW);
+        assert_eq!(vector.len(), u32::MAX);
+    }
 }
@@ -0,0 +1,211 @@
+//! Helpers for setting up the mocked VM in unit tests.
+use crate::tests::*;
+use crate::types::{Address, Balance, BlockHash, BlockNumber, Gas, TimeStamp};
+
+/// Configures the mocked VM context in one go.
+///
+/// Fields that aren't set keep their default mock values. [`Self::build`] resets all mock state
+/// before applying the configured fields.
+///
+/// # Examples
+/// ```ignore
+/// VmContextBuilder::new()
+///     .caller(alice)
+///     .input(br#"{"amount": 10}"#.to_vec())
+///     .balance(alice, 100)
+///     .build();
+/// ```
+#[derive(Default)]
+pub struct VmContextBuilder {
+    caller: Option<Address>,
+    owner: Option<Address>,
+    instance_address: Option<Address>,
+    input: Option<Vec<u8>>,
+    block_hash: Option<BlockHash>,
+    block_number: Option<BlockNumber>,
+    block_timestamp: Option<TimeStamp>,
+    gas_limit: Option<Gas>,
+    gas_left: Option<Gas>,
+    runtime_version: Option<u64>,
+    write_perm: Option<bool>,
+    balances: Vec<(Address, Balance)>,
+}
+
+impl VmContextBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the address returned by [`crate::caller_address`].
+    pub fn caller(mut self, caller: Address) -> Self {
+        self.caller = Some(caller);
+        self
+    }
+
+    /// Sets the address returned by [`crate::contract_owner_address`].
+    pub fn owner(mut self, owner: Address) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Sets the address returned by [`crate::contract_instance_address`].
+    pub fn instance_address(mut self, instance_address: Address) -> Self {
+        self.instance_address = Some(instance_address);
+        self
+    }
+
+    /// Sets the data returned by [`crate::input`].
+    pub fn input(mut self, input: Vec<u8>) -> Self {
+        self.input = Some(input);
+        self
+    }
+
+    /// Sets the value returned by [`crate::block_hash`].
+    pub fn block_hash(mut self, block_hash: BlockHash) -> Self {
+        self.block_hash = Some(block_hash);
+        self
+    }
+
+    /// Sets the value returned by [`crate::block_number`].
+    pub fn block_number(mut self, block_number: BlockNumber) -> Self {
+        self.block_number = Some(block_number);
+        self
+    }
+
+    /// Sets the value returned by [`crate::block_timestamp`].
+    pub fn block_timestamp(mut self, block_timestamp: TimeStamp) -> Self {
+        self.block_timestamp = Some(block_timestamp);
+        self
+    }
+
+    /// Sets the value returned by [`crate::gas_limit`].
+    pub fn gas_limit(mut self, gas_limit: Gas) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    /// Sets the value returned by [`crate::gas_left`].
+    pub fn gas_left(mut self, gas_left: Gas) -> Self {
+        self.gas_left = Some(gas_left);
+        self
+    }
+
+    /// Sets the value returned by [`crate::runtime_version`].
+    pub fn runtime_version(mut self, runtime_version: u64) -> Self {
+        self.runtime_version = Some(runtime_version);
+        self
+    }
+
+    /// Sets the value returned by [`crate::storage_write_perm`].
+    pub fn write_perm(mut self, write_perm: bool) -> Self {
+        self.write_perm = Some(write_perm);
+        self
+    }
+
+    /// Sets the balance of `address` in the mocked ledger. Can be called multiple times.
+    pub fn balance(mut self, address: Address, balance: Balance) -> Self {
+        self.balances.push((address, balance));
+        self
+    }
+
+    /// Resets the mock state and applies the configured fields.
+    pub fn build(self) {
+        reset_mock();
+
+        if let Some(caller) = self.caller {
+            set_mock_caller_address(caller.to_vec());
+        }
+        if let Some(owner) = self.owner {
+            set_mock_contract_owner_address(owner.to_vec());
+        }
+        if let Some(instance_address) = self.instance_address {
+            set_mock_contract_instance_address(instance_address.to_vec());
+        }
+        if let Some(input) = self.input {
+            set_mock_input(input);
+        }
+        if let Some(block_hash) = self.block_hash {
+            set_mock_block_hash(block_hash);
+        }
+        if let Some(block_number) = self.block_number {
+            set_mock_block_number(block_number);
+        }
+        if let Some(block_timestamp) = self.block_timestamp {
+            set_mock_block_timestamp(block_timestamp);
+        }
+        if let Some(gas_limit) = self.gas_limit {
+            set_mock_gas_limit(gas_limit);
+        }
+        if let Some(gas_left) = self.gas_left {
+            set_mock_gas_left(gas_left);
+        }
+        if let Some(runtime_version) = self.runtime_version {
+            set_mock_runtime_version(runtime_version);
+        }
+        if let Some(write_perm) = self.write_perm {
+            set_mock_write_perm(write_perm);
+        }
+        for (address, balance) in self.balances {
+            set_mock_balance(address, balance);
+        }
+    }
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(byte: u8) -> Address {
+        Address::from([byte; 20])
+    }
+
+    #[test]
+    fn test_build_context() {
+        VmContextBuilder::new()
+            .caller(address(1))
+            .owner(address(2))
+            .instance_address(address(3))
+            .input(b"input".to_vec())
+            .block_hash([4; 32])
+            .block_number(5)
+            .block_timestamp(6)
+            .gas_limit(700)
+            .gas_left(600)
+            .runtime_version(2)
+            .write_perm(false)
+            .balance(address(1), 8)
+            .balance(address(3), 9)
+            .build();
+
+        assert_eq!(crate::caller_address(), address(1));
+        assert_eq!(crate::contract_owner_address(), address(2));
+        assert_eq!(crate::contract_instance_address(), address(3));
+        assert_eq!(crate::input(), Some(b"input".to_vec()));
+        assert_eq!(crate::block_hash(), [4; 32]);
+        assert_eq!(crate::block_number(), 5);
+        assert_eq!(crate::block_timestamp(), 6);
+        assert_eq!(crate::gas_limit(), 700);
+        assert_eq!(crate::gas_left(), 600);
+        assert_eq!(crate::runtime_version(), 2);
+        assert!(!crate::storage_write_perm());
+        assert_eq!(crate::address_balance(&address(1)), 8);
+        assert_eq!(crate::contract_instance_balance(), 9);
+    }
+
+    #[test]
+    fn test_build_resets() {
+        VmContextBuilder::new()
+            .caller(address(1))
+            .block_number(5)
+            .build();
+        crate::storage_write(b"key", b"value");
+
+        VmContextBuilder::new().build();
+
+        assert_ne!(crate::caller_address(), address(1));
+        assert_eq!(crate::block_number(), 0);
+        assert!(crate::storage_read(b"key").is_none());
+    }
+}
@@ -0,0 +1,141 @@
+//! A lock that opens at a given block timestamp.
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::types::TimeStamp;
+
+const ERR_LOCKED: &str = "The time lock hasn't expired yet";
+
+/// An unlock time stored under its own storage key.
+///
+/// Keep it in the contract state, set the unlock time with [`Self::lock_until`] and guard the
+/// methods that must wait with [`Self::assert_unlocked`]:
+///
+/// ```ignore
+/// #[derive(BorshSerialize, BorshDeserialize)]
+/// struct Vesting {
+///     cliff: TimeLock,
+///     beneficiary: Address,
+/// }
+///
+/// impl Vesting {
+///     pub fn new(beneficiary: Address, cliff_end: TimeStamp) -> Self {
+///         let mut cliff = TimeLock::new(b"cliff".to_vec());
+///         cliff.lock_until(cliff_end);
+///         Self { cliff, beneficiary }
+///     }
+///
+///     pub fn claim(&mut self) {
+///         self.cliff.assert_unlocked();
+///         // ...
+///     }
+/// }
+/// ```
+///
+/// The times are compared with [`crate::block_timestamp`], so they are in its unit. Setting the
+/// unlock time isn't restricted by itself, guard the methods calling [`Self::lock_until`] with
+/// [`crate::assert_owner`] if needed.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct TimeLock {
+    key: Box<[u8]>,
+}
+
+impl TimeLock {
+    /// Creates a lock that keeps the unlock time under `key`. The lock is open until
+    /// [`Self::lock_until`] is called.
+    pub fn new(key: Vec<u8>) -> Self {
+        Self {
+            key: key.into_boxed_slice(),
+        }
+    }
+
+    /// Returns the unlock time, or `None` if the lock was never set.
+    pub fn unlock_time(&self) -> Option<TimeStamp> {
+        crate::storage_read(&self.key).map(|bytes| {
+            TimeStamp::try_from_slice(&bytes)
+                .unwrap_or_else(|_| crate::panic("Cannot deserialize the unlock time"))
+        })
+    }
+
+    /// Keeps the lock closed until the block timestamp reaches `timestamp`. Replaces the previous
+    /// unlock time, so the lock can be extended or shortened.
+    pub fn lock_until(&mut self, timestamp: TimeStamp) {
+        crate::storage_write(&self.key, &timestamp.to_le_bytes());
+    }
+
+    /// Returns `true` if the block timestamp reached the unlock time or the lock was never set.
+    pub fn is_unlocked(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Panics with "The time lock hasn't expired yet" unless the lock is open.
+    pub fn assert_unlocked(&self) {
+        if !self.is_unlocked() {
+            crate::panic(ERR_LOCKED);
+        }
+    }
+
+    /// Returns the time left until the lock opens, `0` if it's already open.
+    pub fn remaining(&self) -> TimeStamp {
+        self.unlock_time().map_or(0, |unlock_time| {
+            unlock_time.saturating_sub(crate::block_timestamp())
+        })
+    }
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::*;
+    use super::*;
+
+    #[test]
+    fn test_never_locked() {
+        let lock = TimeLock::new(b"lock".to_vec());
+        assert_eq!(lock.unlock_time(), None);
+        assert!(lock.is_unlocked());
+        assert_eq!(lock.remaining(), 0);
+        lock.assert_unlocked();
+    }
+
+    #[test]
+    fn test_unlock_boundary() {
+        set_mock_block_timestamp(1_000);
+        let mut lock = TimeLock::new(b"lock".to_vec());
+        lock.lock_until(1_500);
+        assert_eq!(lock.unlock_time(), Some(1_500));
+
+        assert!(!lock.is_unlocked());
+        assert_eq!(lock.remaining(), 500);
+
+        set_mock_block_timestamp(1_499);
+        assert!(!lock.is_unlocked());
+        assert_eq!(lock.remaining(), 1);
+        assert!(std::panic::catch_unwind(|| lock.assert_unlocked()).is_err());
+        assert_eq!(get_mock_msgs().pop().unwrap(), ERR_LOCKED);
+
+        set_mock_block_timestamp(1_500);
+        assert!(lock.is_unlocked());
+        assert_eq!(lock.remaining(), 0);
+        lock.assert_unlocked();
+
+        set_mock_block_timestamp(2_000);
+        assert!(lock.is_unlocked());
+        assert_eq!(lock.remaining(), 0);
+    }
+
+    #[test]
+    fn test_persistence() {
+        set_mock_block_timestamp(1_000);
+        let mut lock = TimeLock::new(b"lock".to_vec());
+        lock.lock_until(2_000);
+
+        // The unlock time is kept in the storage, not in the struct
+        let state = lock.try_to_vec().unwrap();
+        let mut lock = TimeLock::try_from_slice(&state).unwrap();
+        assert_eq!(lock.remaining(), 1_000);
+
+        lock.lock_until(500);
+        assert!(lock.is_unlocked());
+    }
+}
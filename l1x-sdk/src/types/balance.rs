@@ -0,0 +1,142 @@
+//! Conversions between [`Balance`] and human readable decimal strings.
+use super::Balance;
+
+/// Formats `amount` of the smallest units as a decimal number with `decimals` fractional digits.
+///
+/// Trailing zeros of the fractional part are trimmed and the decimal point is omitted for whole
+/// numbers. No floating point math is involved, so the result is exact.
+///
+/// # Examples
+/// ```
+/// use l1x_sdk::types::format_balance;
+///
+/// assert_eq!(format_balance(1_500_000_000_000_000_000, 18), "1.5");
+/// assert_eq!(format_balance(1, 6), "0.000001");
+/// assert_eq!(format_balance(2_000_000, 6), "2");
+/// ```
+pub fn format_balance(amount: Balance, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    let digits = amount.to_string();
+    let digits = if digits.len() <= decimals {
+        format!("{}{}", "0".repeat(decimals + 1 - digits.len()), digits)
+    } else {
+        digits
+    };
+
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
+
+/// Parses a decimal number with up to `decimals` fractional digits into the smallest units.
+///
+/// This is the inverse of [`format_balance`]. Returns an error if the string isn't a non-negative
+/// decimal number, has more than `decimals` fractional digits or doesn't fit into [`Balance`].
+///
+/// # Examples
+/// ```
+/// use l1x_sdk::types::parse_balance;
+///
+/// assert_eq!(parse_balance("1.5", 18), Ok(1_500_000_000_000_000_000));
+/// assert_eq!(parse_balance("0.000001", 6), Ok(1));
+/// assert!(parse_balance("0.0000001", 6).is_err());
+/// ```
+pub fn parse_balance(s: &str, decimals: u8) -> Result<Balance, String> {
+    let (integer, fraction) = s.split_once('.').unwrap_or((s, ""));
+    let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if (integer.is_empty() && fraction.is_empty()) || !is_digits(integer) || !is_digits(fraction) {
+        return Err(format!("Can't parse balance from string {}", s));
+    }
+    if fraction.len() > decimals as usize {
+        return Err(format!(
+            "Balance {} has more than {} fractional digits",
+            s, decimals
+        ));
+    }
+
+    let digits = format!(
+        "{}{}{}",
+        integer,
+        fraction,
+        "0".repeat(decimals as usize - fraction.len())
+    );
+    let digits = digits.trim_start_matches('0');
+    if digits.is_empty() {
+        return Ok(0);
+    }
+    digits
+        .parse::<Balance>()
+        .map_err(|_| format!("Balance {} is too large", s))
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE_L1X: Balance = 1_000_000_000_000_000_000;
+
+    #[test]
+    fn test_format_balance_18_decimals() {
+        assert_eq!(format_balance(0, 18), "0");
+        assert_eq!(format_balance(1, 18), "0.000000000000000001");
+        assert_eq!(format_balance(ONE_L1X, 18), "1");
+        assert_eq!(format_balance(ONE_L1X / 2, 18), "0.5");
+        assert_eq!(
+            format_balance(123 * ONE_L1X + 4_500, 18),
+            "123.0000000000000045"
+        );
+        assert_eq!(
+            format_balance(Balance::MAX, 18),
+            "340282366920938463463.374607431768211455"
+        );
+    }
+
+    #[test]
+    fn test_format_balance_6_decimals() {
+        assert_eq!(format_balance(0, 6), "0");
+        assert_eq!(format_balance(1, 6), "0.000001");
+        assert_eq!(format_balance(1_230_000, 6), "1.23");
+        assert_eq!(format_balance(42, 0), "42");
+    }
+
+    #[test]
+    fn test_parse_balance() {
+        assert_eq!(parse_balance("0", 18), Ok(0));
+        assert_eq!(parse_balance("0.0", 18), Ok(0));
+        assert_eq!(parse_balance("1", 18), Ok(ONE_L1X));
+        assert_eq!(parse_balance(".5", 18), Ok(ONE_L1X / 2));
+        assert_eq!(parse_balance("1.", 6), Ok(1_000_000));
+        assert_eq!(parse_balance("0.000001", 6), Ok(1));
+        assert_eq!(parse_balance("1.23", 6), Ok(1_230_000));
+    }
+
+    #[test]
+    fn test_parse_balance_errors() {
+        assert!(parse_balance("", 18).is_err());
+        assert!(parse_balance(".", 18).is_err());
+        assert!(parse_balance("-1", 18).is_err());
+        assert!(parse_balance("1.2.3", 18).is_err());
+        assert!(parse_balance("1e18", 18).is_err());
+        assert!(parse_balance("0.0000001", 6).is_err());
+        assert!(parse_balance("1.5", 0).is_err());
+        assert!(parse_balance("340282366920938463464", 18).is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for amount in [0, 1, 999_999, ONE_L1X, 123 * ONE_L1X + 4_500, Balance::MAX] {
+            for decimals in [0, 6, 18] {
+                assert_eq!(
+                    parse_balance(&format_balance(amount, decimals), decimals),
+                    Ok(amount)
+                );
+            }
+        }
+    }
+}
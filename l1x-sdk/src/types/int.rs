@@ -7,6 +7,9 @@ use uint::construct_uint;
 macro_rules! impl_str_type {
     ($iden: ident, $ty: tt) => {
         /// [`$&iden`] same as [`$&ty`] but JSON serializer serializes it to a string. The origninal [`$&ty`] value can be accessed by `$&iden.0`
+        ///
+        /// The JSON deserializer accepts both a string and an integer number, e.g. `"123"` and `123`.
+        /// Numbers with a fraction or an exponent are rejected, pass large values as strings.
         #[derive(
             Debug,
             Clone,
@@ -50,10 +53,57 @@ macro_rules! impl_str_type {
             where
                 D: Deserializer<'de>,
             {
-                let s: String = Deserialize::deserialize(deserializer)?;
-                Ok(Self(str::parse::<$ty>(&s).map_err(|err| {
-                    serde::de::Error::custom(err.to_string())
-                })?))
+                struct Visitor;
+
+                impl<'de> serde::de::Visitor<'de> for Visitor {
+                    type Value = $iden;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        f.write_str("an integer or a string with a decimal integer")
+                    }
+
+                    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                        v.parse().map_err(E::custom)
+                    }
+
+                    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                        <$ty>::try_from(v).map($iden).map_err(E::custom)
+                    }
+
+                    fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                        <$ty>::try_from(v).map($iden).map_err(E::custom)
+                    }
+
+                    fn visit_u128<E: serde::de::Error>(self, v: u128) -> Result<Self::Value, E> {
+                        <$ty>::try_from(v).map($iden).map_err(E::custom)
+                    }
+
+                    fn visit_i128<E: serde::de::Error>(self, v: i128) -> Result<Self::Value, E> {
+                        <$ty>::try_from(v).map($iden).map_err(E::custom)
+                    }
+                }
+
+                // Floats aren't accepted: JSON numbers that don't fit into `u64` or `i64` are
+                // parsed as `f64`, which silently loses precision above 2^53
+                deserializer.deserialize_any(Visitor)
+            }
+        }
+
+        impl std::fmt::Display for $iden {
+            /// Formats the value as a decimal number, the same string the JSON serializer produces
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl std::str::FromStr for $iden {
+            type Err = String;
+
+            /// Parses the decimal string representation, the same way the JSON deserializer does
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                str::parse::<$ty>(s)
+                    .map(Self)
+                    .map_err(|err| err.to_string())
             }
         }
     };
@@ -68,10 +118,147 @@ construct_uint! {
     /// `U256` type implementation.
     ///
     /// The type is implemented with [`uint::construct_uint`] crate but serialized to JSON as a decimal string.
+    /// `Display` and `Debug` use the same decimal representation, use [`U256::to_hex_string`] for hex.
+    ///
+    /// Arithmetic operators panic on overflow. For balance math prefer the explicit variants:
+    /// `checked_add`, `checked_sub`, `checked_mul` and `checked_div` return `None` on overflow or division
+    /// by zero, `saturating_add` and `saturating_sub` clamp to the bounds, and [`U256::mul_div`] computes
+    /// `a * b / denom` without overflowing in the intermediate product.
     #[derive(BorshDeserialize, BorshSerialize)]
     pub struct U256(4);
 }
 
+impl U256 {
+    /// Returns `self * mul / denom` rounded down, computing the product with full 512-bit precision.
+    ///
+    /// Returns `None` if `denom` is zero or the result doesn't fit into [`U256`].
+    ///
+    /// # Examples
+    /// ```
+    /// use l1x_sdk::types::U256;
+    ///
+    /// // `U256::MAX * 3` overflows, but the final result fits
+    /// assert_eq!(U256::MAX.mul_div(3.into(), 4.into()), Some(U256::MAX / 4 * 3 + 2));
+    /// assert_eq!(U256::from(1).mul_div(1.into(), 0.into()), None);
+    /// ```
+    pub fn mul_div(self, mul: U256, denom: U256) -> Option<U256> {
+        if denom.is_zero() {
+            return None;
+        }
+        let (high, low) = self.widening_mul(mul);
+        // The quotient fits into 256 bits only if the high half is below the divisor
+        if high >= denom {
+            return None;
+        }
+        // Binary long division of `high ++ low`, the remainder always stays below `denom`
+        let mut remainder = high;
+        let mut quotient = U256::zero();
+        for bit in (0..256).rev() {
+            let carry = remainder.bit(255);
+            remainder <<= 1;
+            if low.bit(bit) {
+                remainder |= U256::one();
+            }
+            if carry || remainder >= denom {
+                remainder = remainder.overflowing_sub(denom).0;
+                quotient |= U256::one() << bit;
+            }
+        }
+        Some(quotient)
+    }
+
+    /// Returns the full 512-bit product of `self` and `other` as the high and the low halves.
+    fn widening_mul(self, other: U256) -> (U256, U256) {
+        let mut words = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let t = self.0[i] as u128 * other.0[j] as u128 + words[i + j] as u128 + carry;
+                words[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            words[i + 4] = carry as u64;
+        }
+        let mut high = [0u64; 4];
+        let mut low = [0u64; 4];
+        high.copy_from_slice(&words[4..]);
+        low.copy_from_slice(&words[..4]);
+        (U256(high), U256(low))
+    }
+
+    /// Parses a hex string, with or without the `0x` prefix.
+    ///
+    /// # Examples
+    /// ```
+    /// use l1x_sdk::types::U256;
+    ///
+    /// assert_eq!(U256::from_hex_str("0xff"), Ok(U256::from(255)));
+    /// assert_eq!(U256::from_hex_str("FF"), Ok(U256::from(255)));
+    /// assert!(U256::from_hex_str("0x").is_err());
+    /// ```
+    pub fn from_hex_str(s: &str) -> Result<U256, String> {
+        let digits = s.strip_prefix("0x").unwrap_or(s);
+        if digits.is_empty() {
+            return Err(format!("Can't create U256 from hex string {}", s));
+        }
+        U256::from_str_radix(digits, 16)
+            .map_err(|err| format!("Can't create U256 from hex string {}: {}", s, err))
+    }
+
+    /// Returns the `0x` prefixed lowercase hex string representation, without leading zeros.
+    ///
+    /// # Examples
+    /// ```
+    /// use l1x_sdk::types::U256;
+    ///
+    /// assert_eq!(U256::from(255).to_hex_string(), "0xff");
+    /// assert_eq!(U256::zero().to_hex_string(), "0x0");
+    /// ```
+    pub fn to_hex_string(&self) -> String {
+        format!("0x{:x}", self)
+    }
+
+    /// Returns the smaller of `self` and `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use l1x_sdk::types::U256;
+    ///
+    /// assert_eq!(U256::from(1).min(U256::from(2)), U256::from(1));
+    /// ```
+    pub fn min(self, other: U256) -> U256 {
+        Ord::min(self, other)
+    }
+
+    /// Returns the larger of `self` and `other`.
+    ///
+    /// # Examples
+    /// ```
+    /// use l1x_sdk::types::U256;
+    ///
+    /// assert_eq!(U256::from(1).max(U256::from(2)), U256::from(2));
+    /// ```
+    pub fn max(self, other: U256) -> U256 {
+        Ord::max(self, other)
+    }
+
+    /// Restricts `self` to the `lo..=hi` range, e.g. to cap a swap amount by the reserves.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `lo > hi`
+    ///
+    /// # Examples
+    /// ```
+    /// use l1x_sdk::types::U256;
+    ///
+    /// assert_eq!(U256::from(15).clamp(U256::from(0), U256::from(10)), U256::from(10));
+    /// ```
+    pub fn clamp(self, lo: U256, hi: U256) -> U256 {
+        Ord::clamp(self, lo, hi)
+    }
+}
+
 impl Serialize for U256 {
     fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
     where
@@ -176,4 +363,184 @@ mod tests {
         test_serde!(I64, i64, i64::max_value());
         test_serde!(I64, i64, i64::min_value());
     }
+
+    #[test]
+    fn test_u256_checked_arithmetic() {
+        let one = U256::from(1);
+
+        assert_eq!(U256::MAX.checked_add(one), None);
+        assert_eq!((U256::MAX - one).checked_add(one), Some(U256::MAX));
+        assert_eq!(U256::zero().checked_sub(one), None);
+        assert_eq!(one.checked_sub(one), Some(U256::zero()));
+        assert_eq!(U256::MAX.checked_mul(2.into()), None);
+        assert_eq!((U256::MAX / 2).checked_mul(2.into()), Some(U256::MAX - one));
+        assert_eq!(one.checked_div(U256::zero()), None);
+        assert_eq!(U256::from(7).checked_div(2.into()), Some(3.into()));
+
+        assert_eq!(U256::MAX.saturating_add(one), U256::MAX);
+        assert_eq!(U256::zero().saturating_sub(one), U256::zero());
+        assert_eq!(U256::from(5).saturating_sub(3.into()), 2.into());
+    }
+
+    #[test]
+    fn test_u256_mul_div() {
+        // 0.3% fee of a huge amount, where `amount * 3` overflows
+        let amount = U256::MAX - 1;
+        assert_eq!(amount.checked_mul(3.into()), None);
+        assert_eq!(
+            amount.mul_div(3.into(), 1000.into()),
+            Some(amount / 1000 * 3 + amount % 1000 * 3 / 1000)
+        );
+
+        // Exact and rounded down results
+        assert_eq!(U256::MAX.mul_div(U256::MAX, U256::MAX), Some(U256::MAX));
+        assert_eq!(U256::from(10).mul_div(10.into(), 3.into()), Some(33.into()));
+
+        // The result doesn't fit
+        assert_eq!(U256::MAX.mul_div(2.into(), 1.into()), None);
+        // Division by zero
+        assert_eq!(U256::from(1).mul_div(1.into(), U256::zero()), None);
+
+        // The 512-bit product is divided exactly
+        let half = U256::one() << 255;
+        assert_eq!(
+            U256::MAX.mul_div(U256::MAX - 1, U256::MAX),
+            Some(U256::MAX - 1)
+        );
+        assert_eq!(half.mul_div(4.into(), 4.into()), Some(half));
+        assert_eq!(half.mul_div(4.into(), 2.into()), None);
+        assert_eq!(half.mul_div(4.into(), 3.into()), Some((half / 3) * 4 + 2));
+
+        // Matches the plain operators whenever the product fits
+        let mut value = U256::MAX / 7;
+        for denom in [1u64, 3, 7, 1000, u64::MAX] {
+            value = value.overflowing_mul(value).0 ^ U256::from(denom);
+            let (a, b) = (value >> 130, value >> 140);
+            assert!(!a.is_zero() && !b.is_zero());
+            assert_eq!(a.mul_div(b, denom.into()), Some(a * b / denom));
+        }
+    }
+
+    #[test]
+    fn test_u256_hex() {
+        let cases = [
+            ("0", "0x0"),
+            ("255", "0xff"),
+            ("1000000000000000000", "0xde0b6b3a7640000"),
+            (
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+                "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            ),
+        ];
+        for (dec, hex) in cases {
+            let value = U256::from_dec_str(dec).unwrap();
+            assert_eq!(value.to_hex_string(), hex);
+            assert_eq!(U256::from_hex_str(hex), Ok(value));
+            assert_eq!(U256::from_hex_str(&hex[2..]), Ok(value));
+            assert_eq!(U256::from_hex_str(hex).unwrap().to_string(), dec);
+        }
+
+        assert_eq!(U256::from_hex_str("0xDE0B6B3A7640000"), Ok(U256::exp10(18)));
+        assert!(U256::from_hex_str("").is_err());
+        assert!(U256::from_hex_str("0xzz").is_err());
+        // 257 bits
+        assert!(U256::from_hex_str(&format!("0x1{}", "0".repeat(64))).is_err());
+
+        // JSON stays decimal
+        assert_eq!(serde_json::to_string(&U256::from(255)).unwrap(), r#""255""#);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("123".parse::<U128>(), Ok(U128(123)));
+        assert_eq!("-123".parse::<I128>(), Ok(I128(-123)));
+        assert_eq!("18446744073709551615".parse::<U64>(), Ok(U64(u64::MAX)));
+        assert_eq!("-9223372036854775808".parse::<I64>(), Ok(I64(i64::MIN)));
+
+        assert!("".parse::<U128>().is_err());
+        assert!("-1".parse::<U128>().is_err());
+        assert!("1.5".parse::<I128>().is_err());
+        assert!("abc".parse::<I64>().is_err());
+        // Overflow
+        assert!("18446744073709551616".parse::<U64>().is_err());
+        assert!("9223372036854775808".parse::<I64>().is_err());
+
+        // The JSON path reports the same errors
+        assert!(serde_json::from_str::<U64>(r#""18446744073709551616""#).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", U256::from(255u8)), "255");
+        assert_eq!(format!("{:?}", U256::from(255u8)), "255");
+        assert_eq!(U256::from(255u8).to_hex_string(), "0xff");
+        assert_eq!(
+            U256::MAX.to_string(),
+            serde_json::to_string(&U256::MAX).unwrap().trim_matches('"')
+        );
+
+        assert_eq!(format!("{}", U128(u128::MAX)), u128::MAX.to_string());
+        assert_eq!(format!("{}", U64(255)), "255");
+        assert_eq!(format!("{}", I128(-5)), "-5");
+        assert_eq!(format!("{}", I64(i64::MIN)), i64::MIN.to_string());
+        // The formatting flags of the inner value are supported
+        assert_eq!(format!("{:>5}", U64(42)), "   42");
+
+        for value in [U128(0), U128(10u128.pow(30))] {
+            let json = serde_json::to_string(&value).unwrap();
+            assert_eq!(json, format!("\"{}\"", value));
+        }
+    }
+
+    #[test]
+    fn test_deserialize_number() {
+        assert_eq!(serde_json::from_str::<U128>(r#""123""#).unwrap(), U128(123));
+        assert_eq!(serde_json::from_str::<U128>("123").unwrap(), U128(123));
+        assert_eq!(serde_json::from_str::<I128>("-123").unwrap(), I128(-123));
+        assert_eq!(
+            serde_json::from_str::<U64>("18446744073709551615").unwrap(),
+            U64(u64::MAX)
+        );
+        assert_eq!(
+            serde_json::from_str::<I64>("-9223372036854775808").unwrap(),
+            I64(i64::MIN)
+        );
+        // Serialized as a string regardless of the input
+        assert_eq!(
+            serde_json::to_string(&serde_json::from_str::<U128>("123").unwrap()).unwrap(),
+            r#""123""#
+        );
+
+        // Out of range
+        assert!(serde_json::from_str::<U128>("-1").is_err());
+        assert!(serde_json::from_str::<I64>("9223372036854775808").is_err());
+        // Floats, including integers too large for `u64` which JSON parses as `f64`
+        assert!(serde_json::from_str::<U128>("1.0").is_err());
+        assert!(serde_json::from_str::<U128>("1e3").is_err());
+        assert!(serde_json::from_str::<U128>("100000000000000000000").is_err());
+        assert!(serde_json::from_str::<U128>("true").is_err());
+    }
+
+    #[test]
+    fn test_u256_min_max_clamp() {
+        let (one, two) = (U256::from(1), U256::from(2));
+        assert_eq!(one.min(two), one);
+        assert_eq!(two.min(one), one);
+        assert_eq!(one.max(two), two);
+        assert_eq!(U256::MAX.max(U256::zero()), U256::MAX);
+
+        let (lo, hi) = (U256::from(10), U256::from(20));
+        assert_eq!(U256::from(5).clamp(lo, hi), lo);
+        assert_eq!(U256::from(10).clamp(lo, hi), lo);
+        assert_eq!(U256::from(15).clamp(lo, hi), U256::from(15));
+        assert_eq!(U256::from(20).clamp(lo, hi), hi);
+        assert_eq!(U256::MAX.clamp(lo, hi), hi);
+        assert_eq!(U256::from(7).clamp(lo, lo), lo);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_u256_clamp_inverted_range() {
+        U256::from(15).clamp(U256::from(20), U256::from(10));
+    }
 }
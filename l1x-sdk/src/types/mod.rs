@@ -1,6 +1,8 @@
 //! Basic types
 
+mod balance;
 mod int;
 mod primitives;
+pub use balance::{format_balance, parse_balance};
 pub use int::{I128, I64, U128, U256, U64};
-pub use primitives::{Address, Balance, BlockHash, BlockNumber, Gas, TimeStamp};
+pub use primitives::{address_0x, Address, Balance, BlockHash, BlockNumber, Gas, TimeStamp};
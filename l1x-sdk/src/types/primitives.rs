@@ -47,6 +47,52 @@ pub type TimeStamp = u128;
 pub struct Address(AddressArray);
 
 impl Address {
+    /// Returns the all-zero address, commonly used as an "unset" value
+    ///
+    /// # Examples
+    /// ```
+    /// use l1x_sdk::types::Address;
+    ///
+    /// assert_eq!(Address::zero().to_string(), "0000000000000000000000000000000000000000");
+    /// ```
+    pub const fn zero() -> Self {
+        Self([0; 20])
+    }
+
+    /// Creates an address from its raw bytes. Unlike `From<[u8; 20]>`, usable in constants
+    ///
+    /// # Examples
+    /// ```
+    /// use l1x_sdk::types::Address;
+    ///
+    /// const FEE_RECIPIENT: Address = Address::from_array([
+    ///     0xa1, 0x1c, 0xe0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    /// ]);
+    ///
+    /// assert_eq!(FEE_RECIPIENT.to_string(), "a11ce00000000000000000000000000000000000");
+    /// ```
+    pub const fn from_array(bytes: AddressArray) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns `true` if this is the [`Address::zero`] address
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0; 20]
+    }
+
+    /// Compares two addresses in constant time.
+    ///
+    /// Unlike `==`, the comparison doesn't stop at the first differing byte, so its timing doesn't
+    /// tell how much of `other` matches. Prefer it in authorization checks.
+    pub fn ct_eq(&self, other: &Address) -> bool {
+        let diff = self
+            .0
+            .iter()
+            .zip(other.0.iter())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        std::hint::black_box(diff) == 0
+    }
+
     /// Returns the hex string representation of [`Address`]
     ///
     /// # Examples
@@ -76,6 +122,86 @@ impl Address {
         self.0.to_vec()
     }
 
+    /// Returns the `0x` prefixed hex string representation of [`Address`] with the
+    /// [EIP-55](https://eips.ethereum.org/EIPS/eip-55) mixed-case checksum
+    ///
+    /// # Examples
+    /// ```
+    /// use l1x_sdk::types::Address;
+    /// let address = Address::try_from("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap();
+    ///
+    /// assert_eq!(address.to_checksum_string(), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+    /// ```
+    ///
+    /// Requires the `crypto` feature.
+    #[cfg(feature = "crypto")]
+    pub fn to_checksum_string(&self) -> String {
+        let lowercase = hex::encode(self.0);
+        let hash = crate::crypto::keccak256(lowercase.as_bytes());
+
+        let mut checksummed = String::with_capacity(2 + lowercase.len());
+        checksummed.push_str("0x");
+        for (i, c) in lowercase.chars().enumerate() {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 {
+                checksummed.push(c.to_ascii_uppercase());
+            } else {
+                checksummed.push(c);
+            }
+        }
+        checksummed
+    }
+
+    /// Parses an [EIP-55](https://eips.ethereum.org/EIPS/eip-55) checksummed hex string, with or
+    /// without the `0x` prefix
+    ///
+    /// Unlike [`TryFrom<&str>`], returns an error if the casing doesn't match the checksum.
+    ///
+    /// # Examples
+    /// ```
+    /// use l1x_sdk::types::Address;
+    ///
+    /// assert!(Address::from_checksum_str("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_ok());
+    /// assert!(Address::from_checksum_str("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").is_err());
+    /// ```
+    ///
+    /// Requires the `crypto` feature.
+    #[cfg(feature = "crypto")]
+    pub fn from_checksum_str(value: &str) -> Result<Self, String> {
+        let address = Self::try_from(value)?;
+        let hex_str = value.strip_prefix("0x").unwrap_or(value);
+        if address.to_checksum_string()[2..] != *hex_str {
+            return Err(format!("Invalid address checksum {}", value));
+        }
+        Ok(address)
+    }
+
+    /// Parses every string of `iter` like [`TryFrom<&str>`]
+    ///
+    /// Stops at the first invalid entry and returns its index together with the error message.
+    ///
+    /// # Examples
+    /// ```
+    /// use l1x_sdk::types::Address;
+    ///
+    /// let addresses = Address::try_from_many(["0x0000000000000000000000000000000000000001"]);
+    /// assert_eq!(addresses.unwrap().len(), 1);
+    ///
+    /// let (index, _) = Address::try_from_many(["0000000000000000000000000000000000000001", "0x"])
+    ///     .unwrap_err();
+    /// assert_eq!(index, 1);
+    /// ```
+    pub fn try_from_many<I, S>(iter: I) -> Result<Vec<Self>, (usize, String)>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        iter.into_iter()
+            .enumerate()
+            .map(|(i, s)| Self::try_from(s.as_ref()).map_err(|e| (i, e)))
+            .collect()
+    }
+
     /// Returns a reference to the inner `[u8; 20]` array
     pub fn as_bytes(&self) -> &AddressArray {
         &self.0
@@ -100,9 +226,22 @@ impl fmt::Debug for Address {
     }
 }
 
+impl Default for Address {
+    /// Returns [`Address::zero`]
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl AsRef<[u8]> for Address {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
 impl From<AddressArray> for Address {
     fn from(address: AddressArray) -> Self {
-        Self(address)
+        Self::from_array(address)
     }
 }
 
@@ -193,6 +332,48 @@ impl<'de> Deserialize<'de> for Address {
     }
 }
 
+/// A serde helper that serializes [`Address`] as `0x` prefixed hex, for JSON APIs and tools that expect it.
+///
+/// By default [`Address`] serializes to unprefixed lowercase hex. Deserialization accepts both forms either way.
+///
+/// # Examples
+/// ```
+/// use l1x_sdk::types::Address;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Transfer {
+///     #[serde(with = "l1x_sdk::types::address_0x")]
+///     to: Address,
+/// }
+///
+/// let transfer = Transfer {
+///     to: Address::try_from("a11ce00000000000000000000000000000000000").unwrap(),
+/// };
+/// assert_eq!(
+///     serde_json::to_string(&transfer).unwrap(),
+///     r#"{"to":"0xa11ce00000000000000000000000000000000000"}"#
+/// );
+/// ```
+pub mod address_0x {
+    use super::Address;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(address: &Address, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!("0x{}", address))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Address, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Address::deserialize(deserializer)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::types::Address;
@@ -252,6 +433,36 @@ mod test {
         assert!(Address::try_from(addr_0x_str_empty.as_str()).is_err());
     }
 
+    #[test]
+    pub fn address_try_from_many() {
+        let strings = vec![
+            "0x1122334455667788990011223344556677889900".to_string(),
+            "1122334455667788990011223344556677889900".to_string(),
+            "0x0000000000000000000000000000000000000000".to_string(),
+        ];
+        let addresses = Address::try_from_many(&strings).unwrap();
+        assert_eq!(
+            addresses,
+            strings
+                .iter()
+                .map(|s| Address::try_from(s).unwrap())
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(Address::try_from_many(Vec::<&str>::new()), Ok(vec![]));
+
+        let strings = [
+            "0x1122334455667788990011223344556677889900",
+            "0x0000000000000000000000000000000000000000",
+            "0x11223344556677889900112233445566778899zz",
+            "0x",
+        ];
+        assert_eq!(
+            Address::try_from_many(strings),
+            Err((2, Address::try_from(strings[2]).unwrap_err()))
+        );
+    }
+
     #[test]
     pub fn address_from_array() {
         let addr_arr: AddressArray = [
@@ -297,4 +508,144 @@ mod test {
             "112233445566778899aabbccddeeff0011223344"
         );
     }
+
+    #[cfg(feature = "crypto")]
+    const CHECKSUMMED: [&str; 4] = [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ];
+
+    #[test]
+    #[cfg(feature = "crypto")]
+    pub fn address_to_checksum_string() {
+        for expected in CHECKSUMMED {
+            let address = Address::try_from(expected.to_lowercase()).unwrap();
+            assert_eq!(address.to_checksum_string(), expected);
+            // Lowercase `to_string` is unchanged
+            assert_eq!(address.to_string(), expected[2..].to_lowercase());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "crypto")]
+    pub fn address_from_checksum_str() {
+        for checksummed in CHECKSUMMED {
+            let address = Address::from_checksum_str(checksummed).unwrap();
+            assert_eq!(address.to_checksum_string(), checksummed);
+            assert_eq!(Address::from_checksum_str(&checksummed[2..]), Ok(address));
+
+            assert!(Address::from_checksum_str(&checksummed.to_lowercase()).is_err());
+        }
+
+        // A single flipped letter case
+        assert!(Address::from_checksum_str("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD").is_err());
+        assert!(Address::from_checksum_str("0x1234").is_err());
+    }
+
+    #[test]
+    pub fn address_zero() {
+        const ZERO: Address = Address::zero();
+
+        assert_eq!(Address::default(), Address::zero());
+        assert_eq!(ZERO.to_vec(), vec![0u8; 20]);
+        assert!(ZERO.is_zero());
+
+        let mut array = [0u8; 20];
+        array[19] = 1;
+        assert!(!Address::from(array).is_zero());
+        assert!(!Address::from([0xff; 20]).is_zero());
+    }
+
+    #[test]
+    pub fn address_serde_0x() {
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct Prefixed {
+            #[serde(with = "super::address_0x")]
+            address: Address,
+        }
+
+        let address = Address::try_from("a11ce00000000000000000000000000000000000").unwrap();
+        let prefixed = Prefixed { address };
+
+        let json = serde_json::to_string(&prefixed).unwrap();
+        assert_eq!(
+            json,
+            r#"{"address":"0xa11ce00000000000000000000000000000000000"}"#
+        );
+        assert_eq!(serde_json::from_str::<Prefixed>(&json).unwrap(), prefixed);
+        assert_eq!(
+            serde_json::from_str::<Prefixed>(
+                r#"{"address":"a11ce00000000000000000000000000000000000"}"#
+            )
+            .unwrap(),
+            prefixed
+        );
+
+        // The default stays unprefixed and accepts both forms
+        assert_eq!(
+            serde_json::to_string(&address).unwrap(),
+            r#""a11ce00000000000000000000000000000000000""#
+        );
+        assert_eq!(
+            serde_json::from_str::<Address>(r#""0xa11ce00000000000000000000000000000000000""#)
+                .unwrap(),
+            address
+        );
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        let base = Address::from([0xa5; 20]);
+        assert!(base.ct_eq(&base));
+        assert!(Address::zero().ct_eq(&Address::default()));
+
+        for i in 0..20 {
+            for bit in 0..8 {
+                let mut bytes = [0xa5; 20];
+                bytes[i] ^= 1 << bit;
+                let other = Address::from(bytes);
+                assert_eq!(base.ct_eq(&other), base == other);
+                assert!(!base.ct_eq(&other));
+            }
+        }
+
+        let address = |seed: u8| {
+            let mut bytes = [0; 20];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = seed.wrapping_mul(i as u8 + 1) ^ 0x5c;
+            }
+            Address::from(bytes)
+        };
+        for seed in 0u8..=255 {
+            let (a, b) = (address(seed), address(seed % 16));
+            assert_eq!(a.ct_eq(&b), a == b);
+        }
+    }
+
+    #[test]
+    fn test_const_from_array() {
+        const ADDRESS: Address = Address::from_array([
+            0xa1, 0x1c, 0xe0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ]);
+
+        assert_eq!(
+            ADDRESS,
+            Address::try_from("a11ce00000000000000000000000000000000000").unwrap()
+        );
+        assert_eq!(Address::from_array([7; 20]), Address::from([7; 20]));
+    }
+
+    #[test]
+    fn test_as_ref() {
+        fn byte_len(bytes: impl AsRef<[u8]>) -> usize {
+            bytes.as_ref().len()
+        }
+
+        let address = Address::try_from("a11ce00000000000000000000000000000000000").unwrap();
+        assert_eq!(byte_len(address), 20);
+        assert_eq!(address.as_ref(), address.as_bytes());
+        assert_eq!(hex::encode(address), address.to_string());
+    }
 }
@@ -2,3 +2,61 @@ mod stable_map;
 pub(crate) use self::stable_map::StableMap;
 mod cache_entry;
 pub(crate) use cache_entry::{CacheEntry, EntryState};
+
+/// Runs `f` and returns the message of the panic instead of unwinding if `f` panics.
+///
+/// This only works where panics unwind, i.e. in native builds such as unit tests. Contracts are
+/// built for wasm with `panic = "abort"`, where a panic terminates the call before this function
+/// can return. The same is true for [`crate::panic`] in any build that calls into the VM. Use it
+/// to test or recover from panics of plain Rust code, not as an error handling strategy of a
+/// deployed contract.
+pub fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).map_err(|payload| {
+        if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Box<dyn Any>".to_string()
+        }
+    })
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+        assert_eq!(
+            catch_panic(|| -> u32 { panic!("boom") }),
+            Err("boom".to_string())
+        );
+
+        let index = 3;
+        let message = catch_panic(|| [1, 2][index]).unwrap_err();
+        assert_eq!(
+            message,
+            "index out of bounds: the len is 2 but the index is 3"
+        );
+
+        let message = catch_panic(|| std::panic::panic_any(7u8)).unwrap_err();
+        assert_eq!(message, "Box<dyn Any>");
+    }
+
+    #[test]
+    fn test_catch_panic_keeps_state() {
+        let mut values = vec![1, 2];
+        let result = catch_panic(|| {
+            values.push(3);
+            if values.len() > 2 {
+                panic!("Too many values: {}", values.len());
+            }
+        });
+        assert_eq!(result, Err("Too many values: 3".to_string()));
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+}
@@ -1,28 +1,53 @@
 use std::cell::RefCell;
 use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
 
+/// A map that hands out references to its values through `&self`.
+///
+/// Every value is allocated separately and the map only keeps a raw pointer to it. Unlike a `Box`,
+/// the pointer isn't asserted to be unique when the `BTreeMap` moves it around while rebalancing,
+/// so the references returned by [`Self::get`] stay valid for as long as `self` is borrowed:
+///
+/// * A value is never replaced or freed through `&self`, [`Self::get`] only inserts new values.
+/// * Values are only freed by [`Self::clear`] and `drop`, which take `&mut self`, so no reference
+///   returned by [`Self::get`] can be alive at that point.
+/// * [`Self::get_mut`] and [`Self::iter_mut`] take `&mut self` as well, so mutable references
+///   never alias the shared ones.
 pub(crate) struct StableMap<K, V> {
-    map: RefCell<BTreeMap<K, Box<V>>>,
+    map: RefCell<BTreeMap<K, NonNull<V>>>,
+    // The map owns the values
+    _marker: PhantomData<Box<V>>,
 }
 
+// SAFETY: The map owns its values like `BTreeMap<K, Box<V>>` would
+unsafe impl<K: Send, V: Send> Send for StableMap<K, V> {}
+
 impl<K: Ord, V> Default for StableMap<K, V> {
     fn default() -> Self {
         Self {
             map: Default::default(),
+            _marker: PhantomData,
         }
     }
 }
 
 impl<K, V> StableMap<K, V> {
+    fn alloc() -> NonNull<V>
+    where
+        V: Default,
+    {
+        NonNull::from(Box::leak(Box::default()))
+    }
+
     pub(crate) fn get(&self, k: K) -> &V
     where
         K: Ord,
         V: Default,
     {
-        let mut map = self.map.borrow_mut();
-        let v: &mut Box<V> = map.entry(k).or_default();
-        let v: &V = &*v;
-        unsafe { &*(v as *const V) }
+        let v = *self.map.borrow_mut().entry(k).or_insert_with(Self::alloc);
+        // SAFETY: The value is alive until `self` is mutably borrowed
+        unsafe { v.as_ref() }
     }
 
     pub(crate) fn get_mut(&mut self, k: K) -> &mut V
@@ -30,11 +55,37 @@ impl<K, V> StableMap<K, V> {
         K: Ord,
         V: Default,
     {
-        &mut *self.map.get_mut().entry(k).or_default()
+        let mut v = *self.map.get_mut().entry(k).or_insert_with(Self::alloc);
+        // SAFETY: `self` is mutably borrowed, so there are no other references to the value
+        unsafe { v.as_mut() }
     }
 
-    pub(crate) fn inner(&mut self) -> &mut BTreeMap<K, Box<V>> {
-        self.map.get_mut()
+    /// Calls `f` with the value by the key if the key is present, without inserting it.
+    pub(crate) fn map_existing<R, F>(&self, k: &K, f: F) -> Option<R>
+    where
+        K: Ord,
+        F: FnOnce(&V) -> R,
+    {
+        let v = self.map.borrow().get(k).copied()?;
+        // SAFETY: The value is alive until `self` is mutably borrowed
+        Some(f(unsafe { v.as_ref() }))
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> {
+        self.map
+            .get_mut()
+            .iter_mut()
+            // SAFETY: `self` is mutably borrowed and each value is yielded once
+            .map(|(k, v)| (k, unsafe { v.as_mut() }))
+    }
+
+    /// Removes and drops all the values.
+    pub(crate) fn clear(&mut self) {
+        for (_, v) in std::mem::take(self.map.get_mut()) {
+            // SAFETY: The value was allocated by `Self::alloc` and `self` is mutably borrowed, so
+            // it's no longer referenced
+            drop(unsafe { Box::from_raw(v.as_ptr()) });
+        }
     }
 
     #[allow(dead_code)]
@@ -42,3 +93,67 @@ impl<K, V> StableMap<K, V> {
         self.map.borrow().is_empty()
     }
 }
+
+impl<K, V> Drop for StableMap<K, V> {
+    fn drop(&mut self) {
+        self.clear()
+    }
+}
+
+//====================================================== TESTS =================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_references_survive_inserts() {
+        let map: StableMap<u32, u64> = StableMap::default();
+
+        // Keep references while the inserts below split and rebalance the tree nodes
+        let first = map.get(500);
+        let refs: Vec<&u64> = (0..1000).rev().map(|k| map.get(k)).collect();
+
+        assert_eq!(*first, 0);
+        assert!(refs.iter().all(|v| **v == 0));
+        assert!(std::ptr::eq(first, map.get(500)));
+    }
+
+    #[test]
+    fn test_interleaved_get_and_get_mut() {
+        let mut map: StableMap<u32, u64> = StableMap::default();
+
+        for round in 0..10 {
+            for k in 0..100 {
+                *map.get_mut(k) += 1;
+                assert_eq!(*map.get(k), round + 1);
+                assert_eq!(*map.get(k + 100), round);
+                *map.get_mut(k + 100) += 1;
+            }
+        }
+
+        assert_eq!(map.map_existing(&7, |v| *v), Some(10));
+        assert_eq!(map.map_existing(&1000, |v| *v), None);
+        assert_eq!(map.iter_mut().map(|(_, v)| *v).sum::<u64>(), 2000);
+    }
+
+    #[test]
+    fn test_values_are_dropped() {
+        let counter = Rc::new(());
+        {
+            let mut map: StableMap<u32, Option<Rc<()>>> = StableMap::default();
+            for k in 0..10 {
+                *map.get_mut(k) = Some(counter.clone());
+            }
+            assert_eq!(Rc::strong_count(&counter), 11);
+
+            map.clear();
+            assert!(map.is_empty());
+            assert_eq!(Rc::strong_count(&counter), 1);
+
+            *map.get_mut(0) = Some(counter.clone());
+        }
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}